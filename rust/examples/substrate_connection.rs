@@ -52,8 +52,8 @@ async fn main() -> Result<()> {
     println!("   // Create recipient account ID");
     println!("   // let to = AccountId32::from([0u8; 32]);");
     println!("   // let amount = 1_000_000_000_000u128; // 1 SEL");
-    println!("   // let tx_hash = signed.transfer(to, amount).await?;");
-    println!("   // println!(\"Transaction submitted: {{}}\", tx_hash);\n");
+    println!("   // let result = signed.transfer(to, amount).await?;");
+    println!("   // println!(\"Included in {{:?}}, fee paid: {{:?}}\", result.block_hash(), result.fee);\n");
 
     println!("✅ Example completed successfully!");
     println!("\n�� Next steps:");
@@ -1,8 +1,7 @@
 // Substrate Contracts Example
 // Demonstrates smart contract interaction using the Selendra SDK
 
-use selendra_sdk::substrate::{Connection, keypair_from_string, Contract};
-use sp_runtime::AccountId32;
+use selendra_sdk::substrate::{Connection, keypair_from_string};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -16,11 +15,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Sign connection
     let signed = conn.sign(&keypair)?;
-    println!("✅ Signed connection ready");
+    println!("✅ Signed connection ready for {}", signed.account_id());
+
+    // The examples below need `use selendra_sdk::substrate::{CallLimits, Contract};`
+
+    // Example: Deploy a contract built with cargo-contract
+    // Gas and storage deposit limits are taken from a dry run
+    // let wasm = std::fs::read("./flipper.wasm")?;
+    // let uploaded = Contract::upload_code(&signed, &wasm, "./metadata.json").await?;
+    // println!("📦 Code uploaded: {:?}", uploaded.code_hash);
+    // let deployed = Contract::instantiate(
+    //     &signed, "./metadata.json", uploaded.code_hash, "new", &["false".to_string()], 0, vec![],
+    // ).await?;
+    // println!("🚀 Contract instantiated at {} ({} events)", deployed.address(), deployed.tx.events.len());
 
     // Load contract (requires contract address and metadata file)
     // EXAMPLE - Replace with actual contract address
-    let contract_address = AccountId32::from([0u8; 32]);
+    // let contract_address = sp_runtime::AccountId32::from([0u8; 32]);
     
     // Load contract with metadata file
     // let contract = Contract::new(contract_address, "./metadata.json")?;
@@ -31,10 +42,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // let result = contract.read(&conn.inner, "get_value", &args).await?;
     // println!("📖 Contract read result: {:?}", result);
 
-    // Example: Execute contract call (submits transaction)
-    // let value = 0u128; // SEL to send with call
-    // let tx_hash = contract.exec(&signed.inner, "set_value", &args, value).await?;
-    // println!("✅ Contract executed: {}", tx_hash);
+    // Example: Dry run a message to see its gas, storage deposit and decoded result
+    // let dry_run = contract.dry_run(&conn, &signed.account_id(), "set_value", &args, 0).await?;
+    // println!("⛽ Gas required: {:?}, reverted: {}, result: {}", dry_run.dry_run.gas_required, dry_run.dry_run.reverted, dry_run.value);
+    // let typed: u32 = contract.read_typed(&conn, &signed.account_id(), "get_value", &[]).await?;
+
+    // Example: Execute with limits estimated from a dry run
+    // let tx = contract.exec_with_limits(&signed, "set_value", &args, 0, CallLimits::Estimated).await?;
+    // println!("✅ Contract executed: {:?}", tx.tx_hash);
 
     println!("\n⚠️  Note: This example requires:");
     println!("  1. A deployed ink! smart contract");
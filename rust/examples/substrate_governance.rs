@@ -1,7 +1,7 @@
 // Substrate Governance Example
-// Demonstrates treasury and elections queries using the Selendra SDK
+// Demonstrates treasury, elections and democracy queries using the Selendra SDK
 
-use selendra_sdk::substrate::{Connection, keypair_from_string};
+use selendra_sdk::substrate::{Connection, ReferendumInfo, keypair_from_string};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -35,13 +35,48 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let reserved_validators = conn.get_next_era_reserved_validators().await?;
     println!("  - Next era reserved validators: {}", reserved_validators.len());
 
+    // Query democracy
+    println!("\n🗳️  Democracy:");
+
+    let public_proposals = conn.get_public_proposals().await?;
+    for proposal in &public_proposals {
+        println!(
+            "  - Proposal #{} by {}: deposit {}, {} seconds",
+            proposal.index,
+            proposal.proposer,
+            proposal.deposit,
+            proposal.seconds.len()
+        );
+    }
+
+    let referenda = conn.get_ongoing_referenda().await?;
+    for referendum in &referenda {
+        if let ReferendumInfo::Ongoing { end, tally, .. } = &referendum.info {
+            println!(
+                "  - Referendum #{} ends at block {}: {} ayes, {} nays",
+                referendum.index, end, tally.ayes, tally.nays
+            );
+        }
+    }
+
+    let voting = conn.get_voting_state(&signed.account_id()).await?;
+    println!("  - Our votes: {:?}, locked: {}", voting.votes, voting.locked);
+
+    // Example: Vote aye on the first ongoing referendum
+    // UNCOMMENT to execute
+    // if let Some(referendum) = referenda.first() {
+    //     let vote = AccountVote::Standard { aye: true, conviction: Conviction::Locked1x, balance: 1_000_000_000_000_000_000 };
+    //     let result = signed.democracy_vote(referendum.index, vote).await?;
+    //     println!("✅ Voted: {:?}", result.tx_hash);
+    // }
+
     // Example: Propose treasury spend
     // UNCOMMENT to execute (requires council/sudo)
     // use sp_runtime::AccountId32;
     // let beneficiary = AccountId32::from([0u8; 32]);
     // let amount = 1_000_000_000_000_000_000u128; // 1 SEL
-    // let tx_hash = signed.treasury_propose_spend(amount, beneficiary).await?;
-    // println!("✅ Treasury proposal: {}", tx_hash);
+    // let result = signed.treasury_propose_spend(amount, beneficiary).await?;
+    // println!("✅ Treasury proposal: {:?}", result.tx_hash);
 
     Ok(())
 }
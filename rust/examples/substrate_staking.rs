@@ -31,27 +31,27 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Example: Bond tokens for staking
     // UNCOMMENT to execute (requires sufficient balance)
     // let amount = 1_000_000_000_000_000_000u128; // 1 SEL
-    // let tx_hash = signed.stake_bond(amount).await?;
-    // println!("✅ Bonded tokens: {}", tx_hash);
+    // let result = signed.stake_bond(amount).await?;
+    // println!("✅ Bonded tokens: {:?}", result.tx_hash);
 
     // Example: Nominate validators
     // let nominees = vec![/* validator addresses */];
-    // let tx_hash = signed.stake_nominate(nominees).await?;
-    // println!("✅ Nominated validators: {}", tx_hash);
+    // let result = signed.stake_nominate(nominees).await?;
+    // println!("✅ Nominated validators: {:?}", result.tx_hash);
 
     // Example: Start validating (requires bonded stake)
     // let commission = 10; // 10% commission
-    // let tx_hash = signed.stake_validate(commission).await?;
-    // println!("✅ Started validating: {}", tx_hash);
+    // let result = signed.stake_validate(commission).await?;
+    // println!("✅ Started validating: {:?}", result.tx_hash);
 
     // Example: Bond extra tokens
     // let extra = 500_000_000_000_000_000u128; // 0.5 SEL
-    // let tx_hash = signed.stake_bond_extra(extra).await?;
-    // println!("✅ Bonded extra: {}", tx_hash);
+    // let result = signed.stake_bond_extra(extra).await?;
+    // println!("✅ Bonded extra: {:?}", result.tx_hash);
 
     // Example: Stop nominating/validating
-    // let tx_hash = signed.stake_chill().await?;
-    // println!("✅ Chilled: {}", tx_hash);
+    // let result = signed.stake_chill().await?;
+    // println!("✅ Chilled: {:?}", result.tx_hash);
 
     Ok(())
 }
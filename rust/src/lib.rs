@@ -133,6 +133,11 @@ pub use connection::*;
 #[cfg(all(feature = "evm", feature = "substrate"))]
 pub use unified::*;
 
+// The Substrate types are what `Connection` returns, they shadow the chain
+// agnostic types of the same name
+#[cfg(feature = "substrate")]
+pub use substrate::{BlockInfo, ChainInfo, TransactionStatus};
+
 /// Current version of the SDK
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
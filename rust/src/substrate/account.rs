@@ -1,11 +1,16 @@
 //! Account management for Substrate chains
 
+use crate::substrate::client::Connection;
 use crate::types::{Result, SDKError};
 use sp_core::{sr25519, Pair};
 use sp_runtime::AccountId32;
 use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::{Mutex, MutexGuard};
 
 /// Account manager
+#[derive(Default)]
 pub struct AccountManager {
     accounts: HashMap<AccountId32, AccountData>,
 }
@@ -60,7 +65,7 @@ impl std::fmt::Debug for AccountData {
 }
 
 /// Account metadata
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct AccountMetadata {
     /// Account name
     pub name: Option<String>,
@@ -81,6 +86,116 @@ impl AccountMetadata {
     }
 }
 
+/// Local nonce tracker of one signer
+///
+/// The nonce is read from `system_accountNextIndex` on first use and then
+/// counted locally, so transactions submitted concurrently get consecutive
+/// nonces instead of all reading the same one from the node. Clones share the
+/// same counter. After a submission is rejected, or a transaction is dropped
+/// or found invalid, the counter is reset and read from the node again.
+#[derive(Debug, Clone, Default)]
+pub struct NonceManager {
+    next: Arc<Mutex<Option<u32>>>,
+}
+
+impl NonceManager {
+    /// Create a nonce manager that reads the nonce from the node on first use
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a nonce manager that hands out `next` without asking a node
+    #[cfg(test)]
+    fn with_next(next: u32) -> Self {
+        Self {
+            next: Arc::new(Mutex::new(Some(next))),
+        }
+    }
+
+    /// Next nonce to be handed out, `None` until it is read from the node
+    pub async fn peek(&self) -> Option<u32> {
+        *self.next.lock().await
+    }
+
+    /// Forget the local nonce, reading it from the node on next use
+    pub async fn reset(&self) {
+        *self.next.lock().await = None;
+    }
+
+    /// Reserve the next nonce of `account`
+    ///
+    /// Other reservations wait until the returned guard is dropped, so nonces
+    /// are handed out and submitted in order. The nonce is only used up once
+    /// `NonceGuard::advance` is called; a guard dropped without it resets the
+    /// manager.
+    pub(crate) async fn reserve(&self, connection: &Connection, account: &AccountId32) -> Result<NonceGuard<'_>> {
+        self.reserve_with(|| connection.get_next_nonce(account)).await
+    }
+
+    /// Reserve the next nonce, reading it with `fetch` if it is not known yet
+    async fn reserve_with<F>(&self, fetch: impl FnOnce() -> F) -> Result<NonceGuard<'_>>
+    where
+        F: Future<Output = Result<u32>>,
+    {
+        let mut next = self.next.lock().await;
+        let nonce = match *next {
+            Some(nonce) => nonce,
+            None => fetch().await?,
+        };
+        *next = Some(nonce);
+        Ok(NonceGuard { next, nonce, advanced: false })
+    }
+}
+
+/// Nonce managers of the signers of a connection, one per account
+///
+/// Shared by clones of a `Connection`, so every `SignedConnection` of the same
+/// account gets the same `NonceManager`, however it was created.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct NonceManagers {
+    managers: Arc<std::sync::Mutex<HashMap<AccountId32, NonceManager>>>,
+}
+
+impl NonceManagers {
+    /// Nonce manager of `account`, created on first use
+    pub(crate) fn get(&self, account: &AccountId32) -> NonceManager {
+        self.managers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(account.clone())
+            .or_default()
+            .clone()
+    }
+}
+
+/// Nonce reserved from a `NonceManager`
+pub(crate) struct NonceGuard<'a> {
+    next: MutexGuard<'a, Option<u32>>,
+    nonce: u32,
+    advanced: bool,
+}
+
+impl NonceGuard<'_> {
+    /// The reserved nonce
+    pub(crate) fn nonce(&self) -> u32 {
+        self.nonce
+    }
+
+    /// Mark the nonce as used, once the node accepted the transaction
+    pub(crate) fn advance(mut self) {
+        *self.next = Some(self.nonce + 1);
+        self.advanced = true;
+    }
+}
+
+impl Drop for NonceGuard<'_> {
+    fn drop(&mut self) {
+        if !self.advanced {
+            *self.next = None;
+        }
+    }
+}
+
 /// Account utility functions
 pub struct AccountUtils;
 
@@ -127,6 +242,7 @@ impl AccountUtils {
 }
 
 /// Account balance tracking
+#[derive(Default)]
 pub struct BalanceTracker {
     balances: HashMap<AccountId32, u128>,
 }
@@ -156,4 +272,82 @@ impl BalanceTracker {
         self.balances.insert(account_id.clone(), new_balance);
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Nonce fetch of a manager that must not ask the node
+    async fn no_fetch() -> Result<u32> {
+        panic!("nonce should not be read from the node")
+    }
+
+    #[tokio::test]
+    async fn test_clones_reserve_consecutive_nonces() {
+        let manager = NonceManager::with_next(7);
+        let clone = manager.clone();
+
+        let first = manager.reserve_with(no_fetch).await.unwrap();
+        assert_eq!(first.nonce(), 7);
+        first.advance();
+
+        let second = clone.reserve_with(no_fetch).await.unwrap();
+        assert_eq!(second.nonce(), 8);
+        second.advance();
+
+        assert_eq!(manager.peek().await, Some(9));
+        println!("✅ Shared nonce test passed");
+    }
+
+    #[tokio::test]
+    async fn test_unused_nonce_resets_manager() {
+        let manager = NonceManager::with_next(7);
+        let clone = manager.clone();
+
+        let guard = clone.reserve_with(no_fetch).await.unwrap();
+        assert_eq!(guard.nonce(), 7);
+        drop(guard);
+        assert_eq!(manager.peek().await, None);
+
+        let refetched = manager.reserve_with(|| async { Ok(12) }).await.unwrap();
+        assert_eq!(refetched.nonce(), 12);
+        println!("✅ Nonce reset test passed");
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_reservations_in_order() {
+        let manager = NonceManager::with_next(0);
+
+        let reservations = (0..8).map(|_| {
+            let manager = manager.clone();
+            async move {
+                let guard = manager.reserve_with(no_fetch).await.unwrap();
+                let nonce = guard.nonce();
+                // Hold the reservation across an await, like a submission does
+                tokio::task::yield_now().await;
+                guard.advance();
+                nonce
+            }
+        });
+
+        let nonces = futures::future::join_all(reservations).await;
+        assert_eq!(nonces, (0..8).collect::<Vec<u32>>());
+        assert_eq!(manager.peek().await, Some(8));
+        println!("✅ Concurrent nonce test passed");
+    }
+
+    #[tokio::test]
+    async fn test_nonce_managers_per_account() {
+        let managers = NonceManagers::default();
+        let alice = AccountId32::from([1u8; 32]);
+        let bob = AccountId32::from([2u8; 32]);
+
+        let manager = managers.get(&alice);
+        manager.reserve_with(|| async { Ok(7) }).await.unwrap().advance();
+
+        assert_eq!(managers.clone().get(&alice).peek().await, Some(8));
+        assert_eq!(managers.get(&bob).peek().await, None);
+        println!("✅ Nonce managers test passed");
+    }
+}
@@ -0,0 +1,419 @@
+//! AlephBFT finality, session committees and validator bans
+//!
+//! Committee and ban state lives in `CommitteeManagement` on current runtimes
+//! and in `Elections` on older ones; queries read whichever pallet the
+//! runtime has.
+
+use crate::substrate::blocks::BlockId;
+use crate::substrate::client::{account_value, to_block_hash, Connection};
+use crate::substrate::types::BlockInfo;
+use crate::substrate::value::DecodedValue;
+use crate::types::{Result, SDKError};
+use futures::StreamExt;
+use scale_codec::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use sp_runtime::AccountId32;
+
+/// Consensus engine id of Aleph justifications
+pub const ALEPH_ENGINE_ID: [u8; 4] = *b"FRNK";
+
+/// Validators of a session, from the `AlephSessionApi` runtime API
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionCommittee {
+    /// Validators taking part in finalization
+    pub finality_committee: Vec<AccountId32>,
+    /// Validators producing blocks
+    pub block_producers: Vec<AccountId32>,
+}
+
+/// `primitives::SessionCommittee`
+#[derive(Decode)]
+struct RawSessionCommittee {
+    finality_committee: Vec<[u8; 32]>,
+    block_producers: Vec<[u8; 32]>,
+}
+
+/// `primitives::SessionValidatorError`
+#[derive(Decode)]
+enum RawSessionValidatorError {
+    SessionNotWithinRange { lower_limit: u32, upper_limit: u32 },
+    Other(Vec<u8>),
+}
+
+/// Committee and non-committee validators of a session
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionValidators {
+    /// Validators in the committee
+    pub committee: Vec<AccountId32>,
+    /// Elected validators outside the committee
+    pub non_committee: Vec<AccountId32>,
+}
+
+impl SessionValidators {
+    fn from_value(value: &DecodedValue) -> Self {
+        let accounts = |name: &str| value.field(name).map(|accounts| accounts.as_account_ids()).unwrap_or_default();
+        Self {
+            committee: accounts("committee"),
+            non_committee: accounts("non_committee"),
+        }
+    }
+
+    /// Whether `validator` is elected for the session, in the committee or not
+    pub fn contains(&self, validator: &AccountId32) -> bool {
+        self.committee.contains(validator) || self.non_committee.contains(validator)
+    }
+}
+
+/// Finality version change scheduled for a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalityVersionChange {
+    /// Version coming into effect
+    pub version: u32,
+    /// Session from which it applies
+    pub session: u32,
+}
+
+/// Finality protocol versions, from the `Aleph` pallet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalityVersion {
+    /// Version of the current session
+    pub current: u32,
+    /// Version of the next session
+    pub next: u32,
+    /// Scheduled version change, if any
+    pub scheduled: Option<FinalityVersionChange>,
+}
+
+/// Why a validator was banned
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BanReason {
+    /// Produced too few blocks for the given number of sessions
+    InsufficientProduction(u32),
+    /// Took too little part in finalization for the given number of sessions
+    InsufficientFinalization(u32),
+    /// Banned by governance, or for a reason this SDK does not know
+    Other(String),
+}
+
+impl BanReason {
+    fn from_value(value: &DecodedValue) -> Option<Self> {
+        let sessions = || value.at(0).and_then(|count| count.as_u128()).map(|count| count as u32);
+        Some(match value.variant_name()? {
+            // Older runtimes only measured block production, as uptime
+            "InsufficientProduction" | "InsufficientUptime" => BanReason::InsufficientProduction(sessions()?),
+            "InsufficientFinalization" => BanReason::InsufficientFinalization(sessions()?),
+            "OtherReason" => BanReason::Other(String::from_utf8_lossy(&value.at(0)?.as_bytes()?).into_owned()),
+            other => BanReason::Other(other.to_string()),
+        })
+    }
+}
+
+/// Active ban of a validator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BanInfo {
+    /// Why the validator was banned
+    pub reason: BanReason,
+    /// Era the ban started in
+    pub start: u32,
+}
+
+/// Thresholds used to ban underperforming block producers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BanConfig {
+    /// Share of the expected blocks a validator must produce, in parts per billion
+    pub minimal_expected_performance: u32,
+    /// Underperforming sessions after which a validator is banned
+    pub underperformed_session_count_threshold: u32,
+    /// Sessions after which underperformance counters are reset
+    pub clean_session_counter_delay: u32,
+    /// Eras a ban lasts
+    pub ban_period: u32,
+}
+
+impl BanConfig {
+    fn from_value(value: &DecodedValue) -> Option<Self> {
+        let number = |name: &str| value.field(name).and_then(|field| field.as_u128()).map(|field| field as u32);
+        Some(Self {
+            minimal_expected_performance: number("minimal_expected_performance")?,
+            underperformed_session_count_threshold: number("underperformed_session_count_threshold")?,
+            clean_session_counter_delay: number("clean_session_counter_delay")?,
+            ban_period: number("ban_period")?,
+        })
+    }
+}
+
+/// Underperformance counters of a validator
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Underperformance {
+    /// Sessions in which the validator produced too few blocks
+    pub block_production: u32,
+    /// Sessions in which the validator took too little part in finalization,
+    /// `None` on runtimes that do not track it
+    pub finalization: Option<u32>,
+}
+
+/// How close a validator is to being banned
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BanRisk {
+    /// Validator stash
+    pub validator: AccountId32,
+    /// Current ban, if the validator is banned
+    pub banned: Option<BanInfo>,
+    /// Underperformance counters
+    pub underperformance: Underperformance,
+    /// Ban thresholds of the runtime
+    pub config: BanConfig,
+}
+
+impl BanRisk {
+    /// Further underperforming sessions after which the validator gets banned
+    pub fn sessions_until_ban(&self) -> u32 {
+        let worst = self.underperformance.block_production.max(self.underperformance.finalization.unwrap_or(0));
+        self.config.underperformed_session_count_threshold.saturating_sub(worst)
+    }
+
+    /// Whether the validator is banned or within `margin` sessions of a ban
+    pub fn is_at_risk(&self, margin: u32) -> bool {
+        self.banned.is_some() || self.sessions_until_ban() <= margin
+    }
+}
+
+/// Finalized block with its Aleph justification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FinalizedBlock {
+    /// The finalized block
+    pub block: BlockInfo,
+    /// Encoded Aleph justification, if the node stores one for this block
+    ///
+    /// Aleph only keeps justifications of some blocks, such as the last block of
+    /// each session; other blocks are finalized by a descendant's justification.
+    pub justification: Option<Vec<u8>>,
+}
+
+impl Connection {
+    /// Pallet holding committee and ban state on this runtime
+    fn committee_pallet(&self) -> &'static str {
+        if self.client().metadata().pallet_by_name("CommitteeManagement").is_some() {
+            "CommitteeManagement"
+        } else {
+            "Elections"
+        }
+    }
+
+    /// Index of the current session
+    pub async fn get_current_session(&self) -> Result<u32> {
+        Ok(self.storage_fetch_or_default("Session", "CurrentIndex", vec![])
+            .await?
+            .as_u128()
+            .unwrap_or(0) as u32)
+    }
+
+    /// Finality committee and block producers of a session
+    ///
+    /// The runtime can only predict sessions within a limited range around the
+    /// current one.
+    pub async fn get_session_committee(&self, session: u32) -> Result<SessionCommittee> {
+        let bytes = self.client().rpc()
+            .state_call_raw("AlephSessionApi_predict_session_committee", Some(&session.encode()), self.at_hash())
+            .await
+            .map_err(|e| SDKError::Query(format!("Failed to query session committee: {}", e)))?;
+        let committee = std::result::Result::<RawSessionCommittee, RawSessionValidatorError>::decode(&mut &bytes[..])
+            .map_err(|e| SDKError::SerializationError(format!("Failed to decode session committee: {}", e)))?;
+
+        match committee {
+            Ok(committee) => Ok(SessionCommittee {
+                finality_committee: committee.finality_committee.into_iter().map(AccountId32::from).collect(),
+                block_producers: committee.block_producers.into_iter().map(AccountId32::from).collect(),
+            }),
+            Err(RawSessionValidatorError::SessionNotWithinRange { lower_limit, upper_limit }) => Err(SDKError::Query(
+                format!("Session {} is outside the predictable range {}..={}", session, lower_limit, upper_limit),
+            )),
+            Err(RawSessionValidatorError::Other(reason)) => Err(SDKError::Query(format!(
+                "Failed to predict committee of session {}: {}",
+                session,
+                String::from_utf8_lossy(&reason)
+            ))),
+        }
+    }
+
+    /// Validators of the current and the next session
+    pub async fn get_session_validators(&self) -> Result<(SessionValidators, SessionValidators)> {
+        let validators = self.storage_fetch_or_default(self.committee_pallet(), "CurrentAndNextSessionValidatorsStorage", vec![]).await?;
+        let session = |name: &str| validators.field(name).map(SessionValidators::from_value).unwrap_or_default();
+        Ok((session("current"), session("next")))
+    }
+
+    /// Finality versions of the current and next session
+    pub async fn get_finality_version(&self) -> Result<FinalityVersion> {
+        let version = |value: DecodedValue| value.as_u128().unwrap_or(0) as u32;
+        let current = version(self.storage_fetch_or_default("Aleph", "FinalityVersion", vec![]).await?);
+        let next = version(self.storage_fetch_or_default("Aleph", "NextFinalityVersion", vec![]).await?);
+        let scheduled = self.storage_fetch("Aleph", "FinalityScheduledVersionChange", vec![])
+            .await?
+            .and_then(|change| {
+                Some(FinalityVersionChange {
+                    version: change.field("version_incoming")?.as_u128()? as u32,
+                    session: change.field("session")?.as_u128()? as u32,
+                })
+            });
+
+        Ok(FinalityVersion { current, next, scheduled })
+    }
+
+    /// Current ban of a validator, `None` if it is not banned
+    pub async fn get_ban_info(&self, validator: &AccountId32) -> Result<Option<BanInfo>> {
+        let ban = self.storage_fetch(self.committee_pallet(), "Banned", vec![account_value(validator)]).await?;
+        Ok(ban.and_then(|ban| {
+            Some(BanInfo {
+                reason: BanReason::from_value(ban.field("reason")?)?,
+                start: ban.field("start")?.as_u128()? as u32,
+            })
+        }))
+    }
+
+    /// Thresholds used to ban underperforming block producers
+    pub async fn get_ban_config(&self) -> Result<BanConfig> {
+        let pallet = self.committee_pallet();
+        let entry = if self.has_storage(pallet, "ProductionBanConfig") { "ProductionBanConfig" } else { "BanConfig" };
+        let config = self.storage_fetch_or_default(pallet, entry, vec![]).await?;
+        BanConfig::from_value(&config).ok_or_else(|| SDKError::Query(format!("Invalid {} storage value", entry)))
+    }
+
+    /// Underperformance counters of a validator in the current counting window
+    pub async fn get_underperformance(&self, validator: &AccountId32) -> Result<Underperformance> {
+        let pallet = self.committee_pallet();
+        let count = |value: DecodedValue| value.as_u128().unwrap_or(0) as u32;
+
+        let block_production = count(
+            self.storage_fetch_or_default(pallet, "UnderperformedValidatorSessionCount", vec![account_value(validator)])
+                .await?,
+        );
+        let finalization = if self.has_storage(pallet, "UnderperformedFinalizerSessionCount") {
+            Some(count(
+                self.storage_fetch_or_default(pallet, "UnderperformedFinalizerSessionCount", vec![account_value(validator)])
+                    .await?,
+            ))
+        } else {
+            None
+        };
+
+        Ok(Underperformance { block_production, finalization })
+    }
+
+    /// Ban state, underperformance and thresholds of a validator, for alerting
+    pub async fn get_ban_risk(&self, validator: &AccountId32) -> Result<BanRisk> {
+        Ok(BanRisk {
+            validator: validator.clone(),
+            banned: self.get_ban_info(validator).await?,
+            underperformance: self.get_underperformance(validator).await?,
+            config: self.get_ban_config().await?,
+        })
+    }
+
+    /// Wait until a block is finalized and fetch its Aleph justification
+    ///
+    /// A block given by hash must end up on the finalized chain, otherwise an
+    /// error is returned once its height is finalized. Wrap the call in a timeout
+    /// to bound the wait.
+    pub async fn wait_for_finalized(&self, block: impl Into<BlockId>) -> Result<FinalizedBlock> {
+        let block = block.into();
+        let number = match block {
+            BlockId::Number(number) => number,
+            BlockId::Hash(hash) => self.get_block(hash)
+                .await?
+                .ok_or_else(|| SDKError::NotFound(format!("Block {:?} not found", hash)))?
+                .number,
+        };
+
+        // Subscribe before checking, so a finalization in between is not missed
+        let mut finalized = self.subscribe_finalized_blocks().await?;
+        if self.get_finalized_block().await?.number < number {
+            loop {
+                match finalized.next().await {
+                    Some(head) => {
+                        if head?.number >= number {
+                            break;
+                        }
+                    }
+                    None => return Err(SDKError::Connection("Finalized block subscription ended".to_string())),
+                }
+            }
+        }
+
+        let finalized = self.get_block(number)
+            .await?
+            .ok_or_else(|| SDKError::NotFound(format!("Finalized block #{} not found", number)))?;
+        if let BlockId::Hash(hash) = block {
+            if hash != finalized.hash {
+                return Err(SDKError::NotFound(format!(
+                    "Block {:?} was not finalized, #{} is {:?}",
+                    hash, number, finalized.hash
+                )));
+            }
+        }
+
+        let justification = self.client().rpc()
+            .block(Some(to_block_hash(&finalized.hash)))
+            .await
+            .map_err(|e| SDKError::Query(format!("Failed to fetch block: {}", e)))?
+            .and_then(|block| block.justifications)
+            .and_then(|justifications| {
+                justifications
+                    .into_iter()
+                    .find(|(engine_id, _)| *engine_id == ALEPH_ENGINE_ID)
+                    .map(|(_, justification)| justification)
+            });
+
+        Ok(FinalizedBlock { block: finalized, justification })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_committee_decoding() {
+        // Finality committee and block producers
+        type Committee = (Vec<[u8; 32]>, Vec<[u8; 32]>);
+        let committee: std::result::Result<Committee, (u8, u32, u32)> =
+            Ok((vec![[1u8; 32]], vec![[1u8; 32], [2u8; 32]]));
+        let decoded = std::result::Result::<RawSessionCommittee, RawSessionValidatorError>::decode(&mut &committee.encode()[..]);
+        let decoded = decoded.unwrap().ok().unwrap();
+        assert_eq!(decoded.finality_committee, vec![[1u8; 32]]);
+        assert_eq!(decoded.block_producers.len(), 2);
+
+        let error: std::result::Result<(), (u8, u32, u32)> = Err((0, 10, 20));
+        let decoded = std::result::Result::<RawSessionCommittee, RawSessionValidatorError>::decode(&mut &error.encode()[..]);
+        assert!(matches!(
+            decoded.unwrap().err().unwrap(),
+            RawSessionValidatorError::SessionNotWithinRange { lower_limit: 10, upper_limit: 20 }
+        ));
+        println!("✅ Session committee decoding test passed");
+    }
+
+    #[test]
+    fn test_ban_risk() {
+        let risk = |production: u32, finalization: Option<u32>| BanRisk {
+            validator: AccountId32::from([1u8; 32]),
+            banned: None,
+            underperformance: Underperformance { block_production: production, finalization },
+            config: BanConfig {
+                underperformed_session_count_threshold: 10,
+                ..Default::default()
+            },
+        };
+
+        assert_eq!(risk(3, None).sessions_until_ban(), 7);
+        assert_eq!(risk(3, Some(8)).sessions_until_ban(), 2);
+        assert!(risk(3, Some(8)).is_at_risk(2));
+        assert!(!risk(3, None).is_at_risk(2));
+
+        let reason = DecodedValue::Variant(
+            "InsufficientUptime".to_string(),
+            Box::new(DecodedValue::Unnamed(vec![DecodedValue::U128(4)])),
+        );
+        assert_eq!(BanReason::from_value(&reason), Some(BanReason::InsufficientProduction(4)));
+        println!("✅ Ban risk test passed");
+    }
+}
@@ -0,0 +1,285 @@
+//! Utility batches of runtime calls
+
+use crate::substrate::calls::Call;
+use crate::substrate::client::SignedConnection;
+use crate::substrate::events::EventDecoder;
+use crate::substrate::types::{DispatchError, FeeEstimate, TxResult};
+use crate::types::{Result, SDKError};
+use serde::{Deserialize, Serialize};
+use sp_runtime::AccountId32;
+
+/// How a batch handles a failing call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BatchMode {
+    /// `Utility::batch`: stop at the first failure, keeping earlier calls
+    Batch,
+    /// `Utility::batch_all`: revert every call if one fails
+    BatchAll,
+    /// `Utility::force_batch`: dispatch every call, ignoring failures
+    ForceBatch,
+}
+
+impl BatchMode {
+    /// Name of the `Utility` call for this mode
+    pub(crate) fn call_name(&self) -> &'static str {
+        match self {
+            BatchMode::Batch => "batch",
+            BatchMode::BatchAll => "batch_all",
+            BatchMode::ForceBatch => "force_batch",
+        }
+    }
+}
+
+/// Failure of a call inside a batch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchFailure {
+    /// Position of the failed call in the batch
+    pub index: u32,
+    /// Error the call failed with
+    pub error: DispatchError,
+}
+
+/// Outcome of a submitted batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchResult {
+    /// Result of the batch extrinsic itself
+    pub tx: TxResult,
+    /// Number of calls of the batch that were submitted
+    pub calls: usize,
+    /// Calls that failed, from `Utility::BatchInterrupted` or `Utility::ItemFailed`
+    ///
+    /// A failing `batch_all` reverts as a whole and reports its error in
+    /// `tx.dispatch_error` instead, as the runtime does not emit the failing index.
+    pub failures: Vec<BatchFailure>,
+}
+
+impl BatchResult {
+    /// Whether the batch was included and every call succeeded
+    pub fn is_success(&self) -> bool {
+        self.tx.is_success() && self.failures.is_empty()
+    }
+
+    /// Build the result from the events of the batch extrinsic
+    fn from_tx(tx: TxResult, calls: usize, decoder: &EventDecoder) -> Result<Self> {
+        let mut failures = Vec::new();
+        let mut dispatched = 0u32;
+        let metadata = decoder.metadata();
+
+        for raw in tx.events.iter().filter(|event| event.pallet == "Utility") {
+            match raw.name.as_str() {
+                "ItemCompleted" => dispatched += 1,
+                "ItemFailed" | "BatchInterrupted" => {
+                    let event = decoder.decode(raw)?;
+                    let index = event.field("index")
+                        .and_then(|index| index.as_u128())
+                        .map_or(dispatched, |index| index as u32);
+                    let error = event.field("error")
+                        .zip(metadata)
+                        .map(|(error, metadata)| DispatchError::from_value(error, metadata))
+                        .unwrap_or_else(|| DispatchError::Other("Unknown".to_string()));
+                    failures.push(BatchFailure { index, error });
+                    dispatched += 1;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self { tx, calls, failures })
+    }
+}
+
+/// Builder collecting calls into a single `Utility` batch extrinsic
+#[derive(Clone)]
+pub struct BatchBuilder {
+    connection: SignedConnection,
+    mode: BatchMode,
+    calls: Vec<Call>,
+}
+
+impl BatchBuilder {
+    /// Add a call to the batch
+    pub fn call(mut self, call: Call) -> Self {
+        self.calls.push(call);
+        self
+    }
+
+    /// Add several calls to the batch
+    pub fn calls(mut self, calls: impl IntoIterator<Item = Call>) -> Self {
+        self.calls.extend(calls);
+        self
+    }
+
+    /// Add a `Balances::transfer_keep_alive` to the batch
+    pub fn transfer(self, to: &AccountId32, amount: u128) -> Self {
+        self.call(Call::transfer_keep_alive(to, amount))
+    }
+
+    /// Number of calls in the batch
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Whether the batch has no calls
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// The batch as a single call, e.g. for nesting in a proxy or multisig call
+    pub fn into_call(self) -> Call {
+        Call::batch(self.mode, self.calls)
+    }
+
+    /// Estimate the fee of the batch
+    pub async fn estimate_fee(&self) -> Result<FeeEstimate> {
+        self.connection.estimate_fee(&Call::batch(self.mode, self.calls.clone())).await
+    }
+
+    /// Sign and submit the batch, waiting for the connection's inclusion level
+    pub async fn submit(self) -> Result<BatchResult> {
+        if self.calls.is_empty() {
+            return Err(SDKError::Validation("Batch has no calls".to_string()));
+        }
+
+        let calls = self.calls.len();
+        let decoder = EventDecoder::from_connection(&self.connection.connection());
+        let tx = self.connection.submit(Call::batch(self.mode, self.calls)).await?;
+        BatchResult::from_tx(tx, calls, &decoder)
+    }
+}
+
+impl SignedConnection {
+    /// Start a batch of calls with the given failure mode
+    pub fn batch(&self, mode: BatchMode) -> BatchBuilder {
+        BatchBuilder {
+            connection: self.clone(),
+            mode,
+            calls: Vec::new(),
+        }
+    }
+
+    /// Start a `Utility::batch_all`, reverting every call if one fails
+    pub fn batch_all(&self) -> BatchBuilder {
+        self.batch(BatchMode::BatchAll)
+    }
+
+    /// Start a `Utility::force_batch`, dispatching every call regardless of failures
+    pub fn force_batch(&self) -> BatchBuilder {
+        self.batch(BatchMode::ForceBatch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::substrate::events::{EventPhase, RawEvent};
+    use crate::substrate::types::TransactionStatus;
+    use crate::substrate::value::test_util;
+    use scale_codec::Encode;
+    use sp_core::H256;
+
+    fn utility_event(name: &str) -> RawEvent {
+        RawEvent {
+            pallet: "Utility".to_string(),
+            name: name.to_string(),
+            data: Vec::new(),
+            phase: EventPhase::ApplyExtrinsic(1),
+            topics: Vec::new(),
+            block_number: None,
+            block_hash: None,
+        }
+    }
+
+    fn included(events: Vec<RawEvent>) -> TxResult {
+        let mut tx = TxResult::submitted(H256::zero());
+        tx.status = TransactionStatus::InBlock { block_hash: H256::repeat_byte(1) };
+        tx.events = events;
+        tx
+    }
+
+    /// Decoder for `Utility` events, with `Balances` errors to resolve module errors against
+    fn utility_decoder() -> EventDecoder {
+        #[derive(scale_info::TypeInfo)]
+        #[allow(dead_code)]
+        enum UtilityEvent {
+            BatchInterrupted { index: u32, error: sp_runtime::DispatchError },
+            BatchCompleted,
+            BatchCompletedWithErrors,
+            ItemCompleted,
+            ItemFailed { error: sp_runtime::DispatchError },
+        }
+
+        #[derive(scale_info::TypeInfo)]
+        #[allow(dead_code)]
+        enum BalancesError {
+            VestingBalance,
+            LiquidityRestrictions,
+            InsufficientBalance,
+        }
+
+        EventDecoder::from_metadata(test_util::metadata(vec![
+            test_util::pallet("Balances", 5, None, Some(scale_info::meta_type::<BalancesError>())),
+            test_util::pallet("Utility", 1, Some(scale_info::meta_type::<UtilityEvent>()), None),
+        ]))
+    }
+
+    fn insufficient_balance() -> sp_runtime::DispatchError {
+        sp_runtime::DispatchError::Module(sp_runtime::ModuleError { index: 5, error: [2, 0, 0, 0], message: None })
+    }
+
+    #[test]
+    fn test_batch_all_success() {
+        let tx = included(vec![utility_event("ItemCompleted"), utility_event("ItemCompleted"), utility_event("BatchCompleted")]);
+
+        let result = BatchResult::from_tx(tx, 2, &EventDecoder::new()).unwrap();
+        assert!(result.is_success());
+        assert!(result.failures.is_empty());
+        println!("✅ Batch result test passed");
+    }
+
+    #[test]
+    fn test_batch_interrupted() {
+        let mut interrupted = utility_event("BatchInterrupted");
+        interrupted.data = (1u32, insufficient_balance()).encode();
+        let tx = included(vec![utility_event("ItemCompleted"), interrupted]);
+
+        let result = BatchResult::from_tx(tx, 3, &utility_decoder()).unwrap();
+        assert!(!result.is_success());
+        assert_eq!(
+            result.failures,
+            vec![BatchFailure {
+                index: 1,
+                error: DispatchError::Module {
+                    pallet: "Balances".to_string(),
+                    error: "InsufficientBalance".to_string(),
+                    docs: String::new(),
+                },
+            }]
+        );
+        println!("✅ Interrupted batch test passed");
+    }
+
+    #[test]
+    fn test_force_batch_item_failed() {
+        let mut failed = utility_event("ItemFailed");
+        failed.data = sp_runtime::DispatchError::BadOrigin.encode();
+        let tx = included(vec![
+            utility_event("ItemCompleted"),
+            failed,
+            utility_event("ItemCompleted"),
+            utility_event("BatchCompletedWithErrors"),
+        ]);
+
+        let result = BatchResult::from_tx(tx, 3, &utility_decoder()).unwrap();
+        assert!(!result.is_success());
+        assert_eq!(result.failures, vec![BatchFailure { index: 1, error: DispatchError::Other("BadOrigin".to_string()) }]);
+        println!("✅ Force batch item failure test passed");
+    }
+
+    #[test]
+    fn test_batch_mode_call_name() {
+        assert_eq!(Call::batch(BatchMode::Batch, vec![]).name(), "batch");
+        assert_eq!(Call::batch(BatchMode::BatchAll, vec![]).name(), "batch_all");
+        assert_eq!(Call::batch(BatchMode::ForceBatch, vec![]).name(), "force_batch");
+        println!("✅ Batch mode test passed");
+    }
+}
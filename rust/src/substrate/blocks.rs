@@ -0,0 +1,133 @@
+//! Block queries and subscriptions for Substrate chains
+
+use crate::substrate::client::{from_block_hash, to_block_hash, Connection};
+use crate::substrate::types::BlockInfo;
+use crate::types::{Result, SDKError};
+use futures::{Stream, StreamExt};
+use sp_core::H256;
+use std::pin::Pin;
+use subxt::config::substrate::{BlakeTwo256, SubstrateHeader};
+use subxt::config::Header;
+
+/// Stream of blocks produced by a block subscription
+pub type BlockStream = Pin<Box<dyn Stream<Item = Result<BlockInfo>> + Send>>;
+
+/// Block reference, either by hash or by number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockId {
+    /// Block hash
+    Hash(H256),
+    /// Block number
+    Number(u64),
+}
+
+impl From<H256> for BlockId {
+    fn from(hash: H256) -> Self {
+        BlockId::Hash(hash)
+    }
+}
+
+impl From<u64> for BlockId {
+    fn from(number: u64) -> Self {
+        BlockId::Number(number)
+    }
+}
+
+impl From<u32> for BlockId {
+    fn from(number: u32) -> Self {
+        BlockId::Number(number as u64)
+    }
+}
+
+/// Header type used by Selendra chains
+type ChainHeader = SubstrateHeader<u32, BlakeTwo256>;
+
+/// Build a `BlockInfo` from a block hash and its header
+fn block_info(hash: &selendra_client::BlockHash, header: &ChainHeader) -> BlockInfo {
+    BlockInfo {
+        hash: from_block_hash(hash),
+        number: header.number as u64,
+        parent_hash: from_block_hash(&header.parent_hash),
+        state_root: from_block_hash(&header.state_root),
+        extrinsics_root: from_block_hash(&header.extrinsics_root),
+    }
+}
+
+impl Connection {
+    /// Resolve a block reference to its hash, `None` if the block is unknown
+    pub async fn get_block_hash(&self, block: impl Into<BlockId>) -> Result<Option<H256>> {
+        match block.into() {
+            BlockId::Hash(hash) => Ok(Some(hash)),
+            BlockId::Number(number) => {
+                let hash = self.client().rpc().block_hash(Some(number.into()))
+                    .await
+                    .map_err(|e| SDKError::Query(format!("Failed to fetch block hash: {}", e)))?;
+                Ok(hash.as_ref().map(from_block_hash))
+            }
+        }
+    }
+
+    /// Get block header information by hash or number
+    pub async fn get_block(&self, block: impl Into<BlockId>) -> Result<Option<BlockInfo>> {
+        let hash = match self.get_block_hash(block).await? {
+            Some(hash) => to_block_hash(&hash),
+            None => return Ok(None),
+        };
+
+        let header = self.client().rpc().header(Some(hash))
+            .await
+            .map_err(|e| SDKError::Query(format!("Failed to fetch block header: {}", e)))?;
+
+        Ok(header.map(|header| block_info(&hash, &header)))
+    }
+
+    /// Get the current best block
+    pub async fn get_latest_block(&self) -> Result<BlockInfo> {
+        let header = self.client().rpc().header(None)
+            .await
+            .map_err(|e| SDKError::Query(format!("Failed to fetch best header: {}", e)))?
+            .ok_or_else(|| SDKError::NotFound("Best block header not found".to_string()))?;
+
+        Ok(block_info(&header.hash(), &header))
+    }
+
+    /// Get the latest finalized block
+    pub async fn get_finalized_block(&self) -> Result<BlockInfo> {
+        let hash = self.client().rpc().finalized_head()
+            .await
+            .map_err(|e| SDKError::Query(format!("Failed to fetch finalized head: {}", e)))?;
+
+        self.get_block(from_block_hash(&hash))
+            .await?
+            .ok_or_else(|| SDKError::NotFound("Finalized block header not found".to_string()))
+    }
+
+    /// Subscribe to new blocks on the best chain
+    pub async fn subscribe_best_blocks(&self) -> Result<BlockStream> {
+        let blocks = self.client().blocks().subscribe_best()
+            .await
+            .map_err(|e| SDKError::Connection(format!("Failed to subscribe to best blocks: {}", e)))?;
+
+        Ok(Box::pin(blocks.map(|block| {
+            block
+                .map(|block| block_info(&block.hash(), block.header()))
+                .map_err(|e| SDKError::Query(format!("Best block subscription failed: {}", e)))
+        })))
+    }
+
+    /// Subscribe to finalized blocks
+    ///
+    /// Finalized blocks are delivered in order without gaps, even when the node
+    /// finalizes several blocks at once.
+    pub async fn subscribe_finalized_blocks(&self) -> Result<BlockStream> {
+        let blocks = self.client().blocks().subscribe_finalized()
+            .await
+            .map_err(|e| SDKError::Connection(format!("Failed to subscribe to finalized blocks: {}", e)))?;
+
+        Ok(Box::pin(blocks.map(|block| {
+            block
+                .map(|block| block_info(&block.hash(), block.header()))
+                .map_err(|e| SDKError::Query(format!("Finalized block subscription failed: {}", e)))
+        })))
+    }
+}
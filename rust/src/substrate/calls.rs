@@ -0,0 +1,598 @@
+//! Runtime calls supported by the SDK
+//!
+//! Calls are built dynamically against the runtime metadata, so the same `Call`
+//! can be submitted directly, nested inside utility or proxy calls, or encoded
+//! for offline signing.
+
+use crate::substrate::batch::BatchMode;
+use crate::substrate::client::{account_value, multi_address_value, Connection};
+use crate::substrate::democracy::{AccountVote, Conviction};
+use crate::substrate::identity::{data_value, IdentityInfo};
+use crate::substrate::multisig::Timepoint;
+use crate::substrate::pools::{BondExtra, PoolState};
+use crate::substrate::proxy::ProxyType;
+use crate::substrate::staking::RewardDestination;
+use crate::substrate::types::Weight;
+use crate::substrate::vesting::VestingSchedule;
+use crate::types::{Result, SDKError};
+use sp_core::H256;
+use sp_runtime::AccountId32;
+use subxt::dynamic::Value;
+use subxt::tx::{DynamicPayload, TxPayload};
+
+/// Dynamic value of a weight
+pub(crate) fn weight_value(weight: &Weight) -> Value {
+    Value::named_composite([
+        ("ref_time", Value::u128(weight.ref_time as u128)),
+        ("proof_size", Value::u128(weight.proof_size as u128)),
+    ])
+}
+
+/// Dynamic value of an `Option`
+pub(crate) fn option_value(value: Option<Value>) -> Value {
+    match value {
+        Some(value) => Value::unnamed_variant("Some", [value]),
+        None => Value::unnamed_variant("None", []),
+    }
+}
+
+/// Dynamic value of a multisig timepoint
+fn timepoint_value(timepoint: &Timepoint) -> Value {
+    Value::named_composite([
+        ("height", Value::u128(timepoint.height as u128)),
+        ("index", Value::u128(timepoint.index as u128)),
+    ])
+}
+
+/// Dynamic value of a list of accounts
+fn accounts_value(accounts: &[AccountId32]) -> Value {
+    Value::unnamed_composite(accounts.iter().map(account_value))
+}
+
+/// A runtime call that can be submitted by a `SignedConnection`
+#[derive(Debug, Clone)]
+pub struct Call {
+    pallet: String,
+    name: String,
+    fields: Vec<Value>,
+}
+
+impl Call {
+    /// Create a call from a pallet name, call name and its fields in declaration order
+    pub fn new(pallet: impl Into<String>, name: impl Into<String>, fields: Vec<Value>) -> Self {
+        Self {
+            pallet: pallet.into(),
+            name: name.into(),
+            fields,
+        }
+    }
+
+    /// Pallet of the call
+    pub fn pallet(&self) -> &str {
+        &self.pallet
+    }
+
+    /// Name of the call
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Transaction payload of the call
+    pub(crate) fn payload(&self) -> DynamicPayload {
+        subxt::dynamic::tx(self.pallet.clone(), self.name.clone(), self.fields.clone())
+    }
+
+    /// The call as a `RuntimeCall` value, for nesting inside another call
+    pub fn into_value(self) -> Value {
+        self.payload().into_value()
+    }
+
+    /// SCALE encoded call data
+    pub(crate) fn encode(&self, metadata: &subxt::Metadata) -> Result<Vec<u8>> {
+        self.payload()
+            .encode_call_data(metadata)
+            .map_err(|e| SDKError::SerializationError(format!("Failed to encode {}::{}: {}", self.pallet, self.name, e)))
+    }
+
+    /// `Balances::transfer_keep_alive`
+    pub fn transfer_keep_alive(dest: &AccountId32, value: u128) -> Self {
+        Self::new("Balances", "transfer_keep_alive", vec![multi_address_value(dest), Value::u128(value)])
+    }
+
+    /// `Balances::transfer_allow_death`
+    pub fn transfer_allow_death(dest: &AccountId32, value: u128) -> Self {
+        Self::new("Balances", "transfer_allow_death", vec![multi_address_value(dest), Value::u128(value)])
+    }
+
+    /// `Balances::transfer_all`
+    pub fn transfer_all(dest: &AccountId32, keep_alive: bool) -> Self {
+        Self::new("Balances", "transfer_all", vec![multi_address_value(dest), Value::bool(keep_alive)])
+    }
+
+    /// `Balances::force_transfer`, which needs root origin
+    pub fn force_transfer(source: &AccountId32, dest: &AccountId32, value: u128) -> Self {
+        Self::new(
+            "Balances",
+            "force_transfer",
+            vec![multi_address_value(source), multi_address_value(dest), Value::u128(value)],
+        )
+    }
+
+    /// `Staking::bond` with rewards paid to the stash and restaked
+    pub fn stake_bond(value: u128) -> Self {
+        Self::new("Staking", "bond", vec![Value::u128(value), Value::unnamed_variant("Staked", [])])
+    }
+
+    /// `Staking::bond_extra`
+    pub fn stake_bond_extra(max_additional: u128) -> Self {
+        Self::new("Staking", "bond_extra", vec![Value::u128(max_additional)])
+    }
+
+    /// `Staking::nominate`
+    pub fn stake_nominate(targets: &[AccountId32]) -> Self {
+        let targets: Vec<Value> = targets.iter().map(multi_address_value).collect();
+        Self::new("Staking", "nominate", vec![Value::unnamed_composite(targets)])
+    }
+
+    /// `Staking::validate` with a commission in percent, capped at 100
+    pub fn stake_validate(commission: u32) -> Self {
+        // Perbill: parts per billion
+        let commission = commission.min(100) as u128 * 10_000_000;
+        let prefs = Value::named_composite([
+            ("commission", Value::unnamed_composite([Value::u128(commission)])),
+            ("blocked", Value::bool(false)),
+        ]);
+        Self::new("Staking", "validate", vec![prefs])
+    }
+
+    /// `Staking::chill`
+    pub fn stake_chill() -> Self {
+        Self::new("Staking", "chill", vec![])
+    }
+
+    /// `Staking::unbond`, scheduling `value` to be unlocked after the bonding duration
+    pub fn stake_unbond(value: u128) -> Self {
+        Self::new("Staking", "unbond", vec![Value::u128(value)])
+    }
+
+    /// `Staking::withdraw_unbonded`, unlocking funds whose bonding duration has passed
+    pub fn stake_withdraw_unbonded(num_slashing_spans: u32) -> Self {
+        Self::new("Staking", "withdraw_unbonded", vec![Value::u128(num_slashing_spans as u128)])
+    }
+
+    /// `Staking::rebond`, bonding again funds that are still unlocking
+    pub fn stake_rebond(value: u128) -> Self {
+        Self::new("Staking", "rebond", vec![Value::u128(value)])
+    }
+
+    /// `Staking::set_payee`
+    pub fn stake_set_payee(payee: RewardDestination) -> Self {
+        Self::new("Staking", "set_payee", vec![payee.to_value()])
+    }
+
+    /// `Staking::set_controller`, making the stash its own controller
+    pub fn stake_set_controller() -> Self {
+        Self::new("Staking", "set_controller", vec![])
+    }
+
+    /// `Staking::payout_stakers`, paying the rewards of a validator and its nominators for an era
+    pub fn stake_payout_stakers(validator_stash: &AccountId32, era: u32) -> Self {
+        Self::new("Staking", "payout_stakers", vec![account_value(validator_stash), Value::u128(era as u128)])
+    }
+
+    /// `Staking::payout_stakers_by_page`, paying the rewards of one exposure page of a validator for an era
+    pub fn stake_payout_stakers_by_page(validator_stash: &AccountId32, era: u32, page: u32) -> Self {
+        Self::new(
+            "Staking",
+            "payout_stakers_by_page",
+            vec![account_value(validator_stash), Value::u128(era as u128), Value::u128(page as u128)],
+        )
+    }
+
+    /// `Treasury::propose_spend`
+    pub fn treasury_propose_spend(value: u128, beneficiary: &AccountId32) -> Self {
+        Self::new("Treasury", "propose_spend", vec![Value::u128(value), multi_address_value(beneficiary)])
+    }
+
+    /// `Treasury::approve_proposal`, for a root or governance origin
+    pub fn treasury_approve_proposal(proposal_id: u32) -> Self {
+        Self::new("Treasury", "approve_proposal", vec![Value::u128(proposal_id as u128)])
+    }
+
+    /// `Treasury::reject_proposal`, slashing the proposer's bond, for a root or governance origin
+    pub fn treasury_reject_proposal(proposal_id: u32) -> Self {
+        Self::new("Treasury", "reject_proposal", vec![Value::u128(proposal_id as u128)])
+    }
+
+    /// `Treasury::spend_local`, approving a native spend paid at the next spend period
+    ///
+    /// Runtimes predating the asset spend flow name this call `spend`; use
+    /// `Call::new("Treasury", "spend", ..)` with the same fields there.
+    pub fn treasury_spend_local(amount: u128, beneficiary: &AccountId32) -> Self {
+        Self::new("Treasury", "spend_local", vec![Value::u128(amount), multi_address_value(beneficiary)])
+    }
+
+    /// `Treasury::spend`, approving a spend of `asset_kind` claimable with `payout`
+    ///
+    /// `asset_kind` and `beneficiary` depend on the runtime's `AssetKind` and
+    /// `BeneficiaryLookup`, e.g. built with `value_from_json`. The spend is valid
+    /// from the given block, or immediately.
+    pub fn treasury_spend(asset_kind: Value, amount: u128, beneficiary: Value, valid_from: Option<u32>) -> Self {
+        Self::new(
+            "Treasury",
+            "spend",
+            vec![
+                asset_kind,
+                Value::u128(amount),
+                beneficiary,
+                option_value(valid_from.map(|block| Value::u128(block as u128))),
+            ],
+        )
+    }
+
+    /// `Treasury::payout`, paying out an approved spend
+    pub fn treasury_payout(index: u32) -> Self {
+        Self::new("Treasury", "payout", vec![Value::u128(index as u128)])
+    }
+
+    /// `Treasury::check_status`, settling the payment state of a spend
+    pub fn treasury_check_status(index: u32) -> Self {
+        Self::new("Treasury", "check_status", vec![Value::u128(index as u128)])
+    }
+
+    /// `Democracy::vote` on a referendum
+    pub fn democracy_vote(ref_index: u32, vote: AccountVote) -> Self {
+        Self::new("Democracy", "vote", vec![Value::u128(ref_index as u128), vote.to_value()])
+    }
+
+    /// `Democracy::delegate`, delegating the voting power of `balance` to `to`
+    pub fn democracy_delegate(to: &AccountId32, conviction: Conviction, balance: u128) -> Self {
+        Self::new(
+            "Democracy",
+            "delegate",
+            vec![multi_address_value(to), conviction.to_value(), Value::u128(balance)],
+        )
+    }
+
+    /// `Democracy::undelegate`
+    pub fn democracy_undelegate() -> Self {
+        Self::new("Democracy", "undelegate", vec![])
+    }
+
+    /// `Democracy::remove_vote`, removing the sender's vote on a referendum
+    pub fn democracy_remove_vote(ref_index: u32) -> Self {
+        Self::new("Democracy", "remove_vote", vec![Value::u128(ref_index as u128)])
+    }
+
+    /// `Democracy::unlock`, removing expired voting locks of `target`
+    pub fn democracy_unlock(target: &AccountId32) -> Self {
+        Self::new("Democracy", "unlock", vec![multi_address_value(target)])
+    }
+
+    /// `Identity::set_identity`, failing if a field is longer than 32 bytes
+    pub fn identity_set_identity(info: &IdentityInfo) -> Result<Self> {
+        Ok(Self::new("Identity", "set_identity", vec![info.to_value()?]))
+    }
+
+    /// `Identity::clear_identity`, removing the sender's identity and sub-identities
+    pub fn identity_clear_identity() -> Self {
+        Self::new("Identity", "clear_identity", vec![])
+    }
+
+    /// `Identity::request_judgement` from registrar `reg_index`, paying at most `max_fee`
+    pub fn identity_request_judgement(reg_index: u32, max_fee: u128) -> Self {
+        Self::new(
+            "Identity",
+            "request_judgement",
+            vec![Value::u128(reg_index as u128), Value::u128(max_fee)],
+        )
+    }
+
+    /// `Identity::set_subs`, replacing the sender's sub-identities
+    pub fn identity_set_subs(subs: &[(AccountId32, String)]) -> Result<Self> {
+        let subs = subs
+            .iter()
+            .map(|(sub, name)| Ok(Value::unnamed_composite([account_value(sub), data_value(Some(name.as_str()))?])))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::new("Identity", "set_subs", vec![Value::unnamed_composite(subs)]))
+    }
+
+    /// `Identity::add_sub`, adding a sub-identity named `name`
+    pub fn identity_add_sub(sub: &AccountId32, name: &str) -> Result<Self> {
+        Ok(Self::new("Identity", "add_sub", vec![multi_address_value(sub), data_value(Some(name))?]))
+    }
+
+    /// `Identity::rename_sub`
+    pub fn identity_rename_sub(sub: &AccountId32, name: &str) -> Result<Self> {
+        Ok(Self::new("Identity", "rename_sub", vec![multi_address_value(sub), data_value(Some(name))?]))
+    }
+
+    /// `Identity::remove_sub`
+    pub fn identity_remove_sub(sub: &AccountId32) -> Self {
+        Self::new("Identity", "remove_sub", vec![multi_address_value(sub)])
+    }
+
+    /// `Identity::quit_sub`, removing the sender as a sub-identity of its parent
+    pub fn identity_quit_sub() -> Self {
+        Self::new("Identity", "quit_sub", vec![])
+    }
+
+    /// `Vesting::vest`
+    pub fn vesting_vest() -> Self {
+        Self::new("Vesting", "vest", vec![])
+    }
+
+    /// `Vesting::vest_other`
+    pub fn vesting_vest_other(target: &AccountId32) -> Self {
+        Self::new("Vesting", "vest_other", vec![multi_address_value(target)])
+    }
+
+    /// `Vesting::vested_transfer`
+    pub fn vesting_vested_transfer(target: &AccountId32, schedule: VestingSchedule) -> Self {
+        Self::new("Vesting", "vested_transfer", vec![multi_address_value(target), schedule.to_value()])
+    }
+
+    /// `Contracts::call` with already encoded message data
+    pub fn contract_call(
+        dest: &AccountId32,
+        value: u128,
+        gas_limit: Weight,
+        storage_deposit_limit: Option<u128>,
+        data: Vec<u8>,
+    ) -> Self {
+        Self::new(
+            "Contracts",
+            "call",
+            vec![
+                multi_address_value(dest),
+                Value::u128(value),
+                weight_value(&gas_limit),
+                option_value(storage_deposit_limit.map(Value::u128)),
+                Value::from_bytes(data),
+            ],
+        )
+    }
+
+    /// `Contracts::upload_code`, requiring deterministic code
+    pub fn contract_upload_code(code: Vec<u8>, storage_deposit_limit: Option<u128>) -> Self {
+        Self::new(
+            "Contracts",
+            "upload_code",
+            vec![
+                Value::from_bytes(code),
+                option_value(storage_deposit_limit.map(Value::u128)),
+                Value::unnamed_variant("Enforced", []),
+            ],
+        )
+    }
+
+    /// `Contracts::instantiate` of already uploaded code with encoded constructor data
+    pub fn contract_instantiate(
+        value: u128,
+        gas_limit: Weight,
+        storage_deposit_limit: Option<u128>,
+        code_hash: H256,
+        data: Vec<u8>,
+        salt: Vec<u8>,
+    ) -> Self {
+        Self::new(
+            "Contracts",
+            "instantiate",
+            vec![
+                Value::u128(value),
+                weight_value(&gas_limit),
+                option_value(storage_deposit_limit.map(Value::u128)),
+                Value::from_bytes(code_hash),
+                Value::from_bytes(data),
+                Value::from_bytes(salt),
+            ],
+        )
+    }
+
+    /// `Contracts::instantiate_with_code` with encoded constructor data
+    pub fn contract_instantiate_with_code(
+        value: u128,
+        gas_limit: Weight,
+        storage_deposit_limit: Option<u128>,
+        code: Vec<u8>,
+        data: Vec<u8>,
+        salt: Vec<u8>,
+    ) -> Self {
+        Self::new(
+            "Contracts",
+            "instantiate_with_code",
+            vec![
+                Value::u128(value),
+                weight_value(&gas_limit),
+                option_value(storage_deposit_limit.map(Value::u128)),
+                Value::from_bytes(code),
+                Value::from_bytes(data),
+                Value::from_bytes(salt),
+            ],
+        )
+    }
+
+    /// `Utility::batch`, `batch_all` or `force_batch` of the given calls
+    pub fn batch(mode: BatchMode, calls: Vec<Call>) -> Self {
+        let calls: Vec<Value> = calls.into_iter().map(Call::into_value).collect();
+        Self::new("Utility", mode.call_name(), vec![Value::unnamed_composite(calls)])
+    }
+
+    /// `Multisig::as_multi`, approving and, on the final approval, dispatching `call`
+    ///
+    /// `other_signatories` must be sorted and exclude the sender. `timepoint` is
+    /// `None` for the first approval and the timepoint of the pending operation after.
+    pub fn multisig_as_multi(
+        threshold: u16,
+        other_signatories: &[AccountId32],
+        timepoint: Option<Timepoint>,
+        call: Call,
+        max_weight: Weight,
+    ) -> Self {
+        Self::new(
+            "Multisig",
+            "as_multi",
+            vec![
+                Value::u128(threshold as u128),
+                accounts_value(other_signatories),
+                option_value(timepoint.as_ref().map(timepoint_value)),
+                call.into_value(),
+                weight_value(&max_weight),
+            ],
+        )
+    }
+
+    /// `Multisig::approve_as_multi`, approving a call by its hash without dispatching it
+    pub fn multisig_approve_as_multi(
+        threshold: u16,
+        other_signatories: &[AccountId32],
+        timepoint: Option<Timepoint>,
+        call_hash: [u8; 32],
+        max_weight: Weight,
+    ) -> Self {
+        Self::new(
+            "Multisig",
+            "approve_as_multi",
+            vec![
+                Value::u128(threshold as u128),
+                accounts_value(other_signatories),
+                option_value(timepoint.as_ref().map(timepoint_value)),
+                Value::from_bytes(call_hash),
+                weight_value(&max_weight),
+            ],
+        )
+    }
+
+    /// `Multisig::cancel_as_multi`, cancelling a pending operation created by the sender
+    pub fn multisig_cancel_as_multi(
+        threshold: u16,
+        other_signatories: &[AccountId32],
+        timepoint: Timepoint,
+        call_hash: [u8; 32],
+    ) -> Self {
+        Self::new(
+            "Multisig",
+            "cancel_as_multi",
+            vec![
+                Value::u128(threshold as u128),
+                accounts_value(other_signatories),
+                timepoint_value(&timepoint),
+                Value::from_bytes(call_hash),
+            ],
+        )
+    }
+
+    /// `Sudo::sudo`, dispatching `call` with root origin
+    pub fn sudo(call: Call) -> Self {
+        Self::new("Sudo", "sudo", vec![call.into_value()])
+    }
+
+    /// `Proxy::proxy`, dispatching `call` with `real` as origin
+    pub fn proxy(real: &AccountId32, force_proxy_type: Option<ProxyType>, call: Call) -> Self {
+        Self::new(
+            "Proxy",
+            "proxy",
+            vec![
+                multi_address_value(real),
+                option_value(force_proxy_type.map(|proxy_type| proxy_type.to_value())),
+                call.into_value(),
+            ],
+        )
+    }
+
+    /// `Proxy::add_proxy`, registering `delegate` as a proxy of the sender
+    pub fn proxy_add_proxy(delegate: &AccountId32, proxy_type: ProxyType, delay: u32) -> Self {
+        Self::new(
+            "Proxy",
+            "add_proxy",
+            vec![multi_address_value(delegate), proxy_type.to_value(), Value::u128(delay as u128)],
+        )
+    }
+
+    /// `Proxy::remove_proxy`, unregistering a proxy of the sender
+    pub fn proxy_remove_proxy(delegate: &AccountId32, proxy_type: ProxyType, delay: u32) -> Self {
+        Self::new(
+            "Proxy",
+            "remove_proxy",
+            vec![multi_address_value(delegate), proxy_type.to_value(), Value::u128(delay as u128)],
+        )
+    }
+
+    /// `Proxy::create_pure`, spawning a keyless account proxied by the sender
+    pub fn proxy_create_pure(proxy_type: ProxyType, delay: u32, index: u16) -> Self {
+        Self::new(
+            "Proxy",
+            "create_pure",
+            vec![proxy_type.to_value(), Value::u128(delay as u128), Value::u128(index as u128)],
+        )
+    }
+
+    /// `NominationPools::join`, bonding `amount` into pool `pool_id`
+    pub fn pool_join(amount: u128, pool_id: u32) -> Self {
+        Self::new("NominationPools", "join", vec![Value::u128(amount), Value::u128(pool_id as u128)])
+    }
+
+    /// `NominationPools::bond_extra`
+    pub fn pool_bond_extra(extra: BondExtra) -> Self {
+        Self::new("NominationPools", "bond_extra", vec![extra.to_value()])
+    }
+
+    /// `NominationPools::claim_payout`, paying out the pending rewards of the sender
+    pub fn pool_claim_payout() -> Self {
+        Self::new("NominationPools", "claim_payout", vec![])
+    }
+
+    /// `NominationPools::unbond`, unbonding `unbonding_points` of a pool member
+    pub fn pool_unbond(member: &AccountId32, unbonding_points: u128) -> Self {
+        Self::new(
+            "NominationPools",
+            "unbond",
+            vec![multi_address_value(member), Value::u128(unbonding_points)],
+        )
+    }
+
+    /// `NominationPools::withdraw_unbonded`, withdrawing the unlocked funds of a pool member
+    pub fn pool_withdraw_unbonded(member: &AccountId32, num_slashing_spans: u32) -> Self {
+        Self::new(
+            "NominationPools",
+            "withdraw_unbonded",
+            vec![multi_address_value(member), Value::u128(num_slashing_spans as u128)],
+        )
+    }
+
+    /// `NominationPools::create`, creating a pool with the sender as depositor
+    pub fn pool_create(amount: u128, root: &AccountId32, nominator: &AccountId32, bouncer: &AccountId32) -> Self {
+        Self::new(
+            "NominationPools",
+            "create",
+            vec![
+                Value::u128(amount),
+                multi_address_value(root),
+                multi_address_value(nominator),
+                multi_address_value(bouncer),
+            ],
+        )
+    }
+
+    /// `NominationPools::nominate`, setting the validators of a pool
+    pub fn pool_nominate(pool_id: u32, validators: &[AccountId32]) -> Self {
+        Self::new("NominationPools", "nominate", vec![Value::u128(pool_id as u128), accounts_value(validators)])
+    }
+
+    /// `NominationPools::set_state`
+    pub fn pool_set_state(pool_id: u32, state: PoolState) -> Self {
+        Self::new("NominationPools", "set_state", vec![Value::u128(pool_id as u128), state.to_value()])
+    }
+}
+
+impl Connection {
+    /// SCALE encoded call data of a call, as included in an extrinsic
+    pub fn encode_call(&self, call: &Call) -> Result<Vec<u8>> {
+        call.encode(&self.client().metadata())
+    }
+
+    /// Blake2-256 hash of the encoded call, as used by multisig approvals
+    pub fn call_hash(&self, call: &Call) -> Result<[u8; 32]> {
+        Ok(sp_core::hashing::blake2_256(&self.encode_call(call)?))
+    }
+}
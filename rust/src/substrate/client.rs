@@ -1,24 +1,126 @@
 //! Substrate client implementation - wrapper around selendra_client
 
+use crate::substrate::account::{NonceManager, NonceManagers};
+use crate::substrate::blocks::BlockId;
+use crate::substrate::calls::Call;
+use crate::substrate::events::raw_event_from_details;
+use crate::substrate::proxy::ProxyType;
+use crate::substrate::types::{
+    AccountData, AccountInfo, Balance, BalanceFreeze, BalanceLock, DispatchError, TransactionStatus, TxResult};
+use crate::substrate::value::{bounded_items, DecodedValue};
 use crate::types::{Result, SDKError};
-use sp_core::{sr25519, Pair, crypto::Ss58Codec};
+use scale_codec::Encode;
+use sp_core::{sr25519, Pair, H256, crypto::Ss58Codec};
 use sp_runtime::AccountId32;
 use std::str::FromStr;
-use subxt::utils::Static;
+use std::sync::Arc;
+use subxt::blocks::ExtrinsicEvents;
+use subxt::dynamic::Value;
+use subxt::client::OnlineClientT;
+use subxt::tx::{PairSigner, TxProgress};
 
 pub use selendra_client::{AccountId, BlockHash, TxHash, TxStatus};
 
+/// Convert an SDK account id into a selendra_client account id
+pub(crate) fn to_selendra_account(account: &AccountId32) -> selendra_client::AccountId {
+    let acc_bytes: &[u8; 32] = account.as_ref();
+    selendra_client::AccountId::from(*acc_bytes)
+}
+
+/// Convert a selendra_client account id into an SDK account id
+pub(crate) fn from_selendra_account(account: &selendra_client::AccountId) -> AccountId32 {
+    let acc_bytes: &[u8; 32] = account.as_ref();
+    AccountId32::from(*acc_bytes)
+}
+
+/// Account of a pallet or one of its sub-accounts, derived from the pallet's `PalletId`
+/// as `into_sub_account_truncating` does
+pub(crate) fn pallet_account(pallet_id: [u8; 8], sub: impl Encode) -> AccountId32 {
+    let mut bytes = [0u8; 32];
+    let seed = (b"modl", pallet_id, sub).encode();
+    let len = seed.len().min(32);
+    bytes[..len].copy_from_slice(&seed[..len]);
+    AccountId32::from(bytes)
+}
+
+/// Dynamic value of an account id, for building runtime calls
+pub(crate) fn account_value(account: &AccountId32) -> Value {
+    Value::from_bytes(account)
+}
+
+/// Dynamic `MultiAddress::Id` value of an account id
+pub(crate) fn multi_address_value(account: &AccountId32) -> Value {
+    Value::unnamed_variant("Id", [account_value(account)])
+}
+
+/// Convert an SDK hash into a selendra_client block hash
+pub(crate) fn to_block_hash(hash: &H256) -> BlockHash {
+    BlockHash::from_slice(hash.as_bytes())
+}
+
+/// Convert a selendra_client block hash into an SDK hash
+pub(crate) fn from_block_hash(hash: &BlockHash) -> H256 {
+    H256::from_slice(hash.as_ref())
+}
+
+/// Connection to a Selendra node for queries
 #[derive(Clone)]
 pub struct Connection {
     inner: selendra_client::Connection,
+    at: Option<BlockHash>,
+    at_metadata: Arc<tokio::sync::OnceCell<subxt::Metadata>>,
+    nonce_managers: NonceManagers,
 }
 
 impl Connection {
+    /// Connect to the node at the given websocket url
     pub async fn new(url: &str) -> Result<Self> {
         let inner = selendra_client::Connection::new(url).await;
-        Ok(Self { inner })
+        Ok(Self {
+            inner,
+            at: None,
+            at_metadata: Default::default(),
+            nonce_managers: NonceManagers::default(),
+        })
+    }
+
+    /// View of this connection whose queries read the state at the given block
+    ///
+    /// Runtime constants are read from the metadata of that block.
+    ///
+    /// Fails with `SDKError::NotFound` if the block is unknown to the node.
+    pub async fn at(&self, block: impl Into<BlockId>) -> Result<Self> {
+        let block = block.into();
+        let hash = self.get_block_hash(block)
+            .await?
+            .ok_or_else(|| SDKError::NotFound(format!("Block {:?} not found", block)))?;
+
+        Ok(Self {
+            at: Some(to_block_hash(&hash)),
+            at_metadata: Default::default(),
+            ..self.clone()
+        })
     }
 
+    /// Block queries read the state at, `None` for the best block
+    pub fn block(&self) -> Option<H256> {
+        self.at.as_ref().map(from_block_hash)
+    }
+
+    /// Block hash passed to selendra_client queries
+    pub(crate) fn at_hash(&self) -> Option<BlockHash> {
+        self.at
+    }
+
+    /// Underlying subxt client, for queries selendra_client does not wrap
+    pub(crate) fn client(&self) -> &selendra_client::SubxtClient {
+        self.inner.as_client()
+    }
+
+    /// Connection signing transactions with the given key pair
+    ///
+    /// Signed connections of the same account share one `NonceManager`, whether
+    /// they are clones or created by separate calls on this connection or its clones.
     pub fn sign(&self, keypair: &KeyPair) -> Result<SignedConnection> {
         let selendra_keypair = selendra_client::KeyPair::from_str(&keypair.seed)
             .map_err(|e| SDKError::InvalidKey(format!("Failed: {}", e)))?;
@@ -27,26 +129,87 @@ impl Connection {
             inner: selendra_client::SignedConnection {
                 connection: self.inner.clone(),
                 signer: selendra_keypair,
-            }
+            },
+            tx_status: TxStatus::InBlock,
+            proxied: None,
+            at: self.at,
+            nonces: self.nonce_managers.get(&keypair.account_id()),
+            nonce_managers: self.nonce_managers.clone(),
         })
     }
 
+    /// Next nonce of an account, counting its transactions in the node's pool
+    pub async fn get_next_nonce(&self, account: &AccountId32) -> Result<u32> {
+        self.client().rpc()
+            .request::<u32>("system_accountNextIndex", subxt::rpc_params![account.to_ss58check()])
+            .await
+            .map_err(|e| SDKError::Query(format!("Failed to fetch account nonce: {}", e)))
+    }
+
+    /// Locks on the balance of an account, e.g. from staking or voting
+    pub async fn get_balance_locks(&self, account: &AccountId32) -> Result<Vec<BalanceLock>> {
+        let locks = self.storage_fetch_or_default("Balances", "Locks", vec![account_value(account)]).await?;
+        Ok(bounded_items(&locks).into_iter().filter_map(BalanceLock::from_value).collect())
+    }
+
+    /// Account information of any account, with its locks and freezes
+    ///
+    /// An account that does not exist has a zero balance and nonce.
+    pub async fn get_account_info(&self, account: &AccountId32) -> Result<AccountInfo> {
+        let info = self.storage_fetch_or_default("System", "Account", vec![account_value(account)]).await?;
+        let counter = |name: &str| info.field(name).and_then(|counter| counter.as_u128()).unwrap_or(0) as u32;
+        let data = match info.field("data") {
+            Some(data) => AccountData::from_value(data)
+                .ok_or_else(|| SDKError::Query("Invalid System::Account storage value".to_string()))?,
+            None => AccountData::default(),
+        };
+
+        Ok(AccountInfo {
+            nonce: counter("nonce"),
+            consumers: counter("consumers"),
+            providers: counter("providers"),
+            sufficients: counter("sufficients"),
+            data,
+            locks: self.get_balance_locks(account).await?,
+            freezes: self.get_balance_freezes(account).await?,
+        })
+    }
+
+    /// Free balance of any account
+    pub async fn get_free_balance(&self, account: &AccountId32) -> Result<Balance> {
+        Ok(self.get_account_info(account).await?.data.free)
+    }
+
+    /// Balance of any account that can be transferred while keeping it alive
+    pub async fn get_transferable_balance(&self, account: &AccountId32) -> Result<Balance> {
+        let existential_deposit = self.get_existential_deposit().await?;
+        Ok(self.get_account_info(account).await?.transferable(existential_deposit, true))
+    }
+
+    /// Minimum balance an account must hold to exist
+    pub async fn get_existential_deposit(&self) -> Result<Balance> {
+        self.integer_constant("Balances", "ExistentialDeposit").await
+    }
+
+    /// Freezes on the balance of an account, e.g. from nomination pools
+    pub async fn get_balance_freezes(&self, account: &AccountId32) -> Result<Vec<BalanceFreeze>> {
+        if !self.has_storage("Balances", "Freezes") {
+            return Ok(Vec::new());
+        }
+        let freezes = self.storage_fetch_or_default("Balances", "Freezes", vec![account_value(account)]).await?;
+        Ok(bounded_items(&freezes).into_iter().filter_map(BalanceFreeze::from_value).collect())
+    }
+
+    /// Staking ledger summary of an account
     pub async fn get_staking_info(&self, account: AccountId32) -> Result<crate::substrate::types::StakingInfo> {
         use selendra_client::pallets::staking::StakingApi;
 
-        let acc_bytes: &[u8; 32] = account.as_ref();
-        let selendra_account = selendra_client::AccountId::from(*acc_bytes);
-        let bonded = self.inner.get_bonded(selendra_account, None)
+        let bonded = self.inner.get_bonded(to_selendra_account(&account), self.at)
             .await
-            .map(|acc| {
-                let bonded_bytes: &[u8; 32] = acc.as_ref();
-                AccountId32::from(*bonded_bytes)
-            });
+            .map(|acc| from_selendra_account(&acc));
 
-        let minimum_validator_count = self.inner.get_minimum_validator_count(None).await;
-        let sessions_per_era = self.inner.get_session_per_era()
-            .await
-            .map_err(|e| SDKError::Query(format!("Failed: {}", e)))?;
+        let minimum_validator_count = self.inner.get_minimum_validator_count(self.at).await;
+        let sessions_per_era = self.integer_constant("Staking", "SessionsPerEra").await? as u32;
 
         Ok(crate::substrate::types::StakingInfo {
             bonded,
@@ -55,21 +218,83 @@ impl Connection {
         })
     }
 
+    /// Maximum number of nomination targets allowed by the runtime
+    ///
+    /// `None` if the runtime does not declare `Staking::MaxNominations`.
+    pub async fn get_max_nominations(&self) -> Result<Option<u32>> {
+        let declared = self.metadata()
+            .await?
+            .pallet_by_name("Staking")
+            .and_then(|pallet| pallet.constant_by_name("MaxNominations"))
+            .is_some();
+        if !declared {
+            return Ok(None);
+        }
+
+        let max = self.integer_constant("Staking", "MaxNominations").await?;
+        u32::try_from(max)
+            .map(Some)
+            .map_err(|_| SDKError::Query(format!("MaxNominations {} does not fit in a u32", max)))
+    }
+
+    /// Runtime metadata of the block queries read from
+    ///
+    /// Fetched once per `at` view; the best block uses the metadata of the client.
+    pub(crate) async fn metadata(&self) -> Result<subxt::Metadata> {
+        let Some(hash) = self.at else {
+            return Ok(self.client().metadata());
+        };
+        self.at_metadata
+            .get_or_try_init(|| async {
+                self.client().rpc()
+                    .metadata_legacy(Some(hash))
+                    .await
+                    .map_err(|e| SDKError::Query(format!("Failed to fetch metadata at {:?}: {}", hash, e)))
+            })
+            .await
+            .cloned()
+    }
+
+    /// Integer constant of a pallet, from the metadata of the block queries read from
+    pub(crate) async fn integer_constant(&self, pallet: &str, name: &str) -> Result<u128> {
+        let metadata = self.metadata().await?;
+        let constant = metadata.pallet_by_name(pallet)
+            .and_then(|pallet| pallet.constant_by_name(name))
+            .ok_or_else(|| SDKError::Query(format!("Constant {}::{} not found", pallet, name)))?;
+        DecodedValue::decode(&mut constant.value(), constant.ty(), &metadata)?
+            .as_u128()
+            .ok_or_else(|| SDKError::Query(format!("{}::{} is not an integer", pallet, name)))
+    }
+
+    /// `PalletId` constant of a pallet, or `default` if the runtime does not expose it
+    pub(crate) fn pallet_id(&self, pallet: &str, default: [u8; 8]) -> [u8; 8] {
+        let address = subxt::dynamic::constant(pallet, "PalletId");
+        self.client().constants().at(&address)
+            .ok()
+            .and_then(|constant| constant.to_value().ok())
+            .and_then(|value| DecodedValue::from(value).as_bytes())
+            .and_then(|bytes| bytes.try_into().ok())
+            .unwrap_or(default)
+    }
+
+    /// Number of treasury spend proposals made so far
     pub async fn get_treasury_proposals_count(&self) -> Result<u32> {
         use selendra_client::pallets::treasury::TreasuryApi;
-        self.inner.proposals_count(None)
+        self.inner.proposals_count(self.at)
             .await
             .ok_or_else(|| SDKError::Query("Treasury proposals count not found".to_string()))
     }
 
+    /// Indices of the approved treasury proposals
     pub async fn get_treasury_approvals(&self) -> Result<Vec<u32>> {
         use selendra_client::pallets::treasury::TreasuryApi;
-        Ok(self.inner.approvals(None).await)
+        Ok(self.inner.approvals(self.at).await)
     }
 
+    /// Reserved and non-reserved validators of the current era
     pub async fn get_current_era_validators(&self) -> Result<Vec<AccountId32>> {
         use selendra_client::pallets::elections::ElectionsApi;
-        let era_validators = self.inner.get_current_era_validators(None).await;
+        let era_validators = self.inner.get_current_era_validators(self.at).await;
         let mut validators: Vec<AccountId32> = era_validators.reserved
             .into_iter()
             .map(|acc| {
@@ -88,9 +313,10 @@ impl Connection {
         Ok(validators)
     }
 
+    /// Reserved validators of the next era
     pub async fn get_next_era_reserved_validators(&self) -> Result<Vec<AccountId32>> {
         use selendra_client::pallets::elections::ElectionsApi;
-        let validators = self.inner.get_next_era_reserved_validators(None).await;
+        let validators = self.inner.get_next_era_reserved_validators(self.at).await;
         Ok(validators
             .into_iter()
             .map(|acc| {
@@ -99,162 +325,319 @@ impl Connection {
             })
             .collect())
     }
+
+    /// Wait for a submitted extrinsic to reach the given inclusion level and collect its result
+    pub(crate) async fn watch<T, C>(&self, progress: TxProgress<T, C>, status: TxStatus) -> Result<TxResult>
+    where
+        T: subxt::Config<Hash = BlockHash>,
+        C: OnlineClientT<T>,
+    {
+        let tx_hash = from_block_hash(&progress.extrinsic_hash());
+
+        let in_block = match status {
+            TxStatus::Submitted => return Ok(TxResult::submitted(tx_hash)),
+            TxStatus::InBlock => progress.wait_for_in_block().await,
+            TxStatus::Finalized => progress.wait_for_finalized().await,
+        }
+        .map_err(|e| SDKError::Transaction(format!("Failed: {}", e)))?;
+
+        let block_hash = from_block_hash(&in_block.block_hash());
+        let events = in_block.fetch_events()
+            .await
+            .map_err(|e| SDKError::Transaction(format!("Failed to fetch events: {}", e)))?;
+
+        let status = match status {
+            TxStatus::Finalized => TransactionStatus::Finalized { block_hash },
+            _ => TransactionStatus::InBlock { block_hash },
+        };
+        self.tx_result(tx_hash, status, &events)
+    }
+
+    /// Collect fee, events and dispatch error of an included extrinsic
+    fn tx_result<T: subxt::Config>(
+        &self,
+        tx_hash: H256,
+        status: TransactionStatus,
+        events: &ExtrinsicEvents<T>,
+    ) -> Result<TxResult> {
+        let metadata = self.client().metadata();
+        let mut result = TxResult::submitted(tx_hash);
+        result.status = status;
+        result.extrinsic_index = Some(events.extrinsic_index());
+
+        for details in events.iter() {
+            let details = details
+                .map_err(|e| SDKError::Transaction(format!("Failed to decode event: {}", e)))?;
+
+            match (details.pallet_name(), details.variant_name()) {
+                ("TransactionPayment", "TransactionFeePaid") | ("System", "ExtrinsicFailed") => {
+                    let fields: DecodedValue = details.field_values()
+                        .map_err(|e| SDKError::Transaction(format!("Failed to decode event: {}", e)))?
+                        .into();
+                    if let Some(fee) = fields.field("actual_fee").and_then(|fee| fee.as_u128()) {
+                        result.fee = Some(fee);
+                    }
+                    if let Some(error) = fields.field("dispatch_error") {
+                        result.dispatch_error = Some(DispatchError::from_value(error, &metadata));
+                    }
+                }
+                _ => {}
+            }
+
+            result.events.push(raw_event_from_details(&details, None, result.block_hash()));
+        }
+
+        Ok(result)
+    }
 }
 
+/// Connection submitting transactions on behalf of a signer
 #[derive(Clone)]
 pub struct SignedConnection {
     inner: selendra_client::SignedConnection,
+    tx_status: TxStatus,
+    proxied: Option<(AccountId32, Option<ProxyType>)>,
+    at: Option<BlockHash>,
+    nonces: NonceManager,
+    nonce_managers: NonceManagers,
 }
 
 impl SignedConnection {
+    /// Unsigned connection sharing the same node client
+    pub fn connection(&self) -> Connection {
+        Connection {
+            inner: self.inner.connection.clone(),
+            at: self.at,
+            at_metadata: Default::default(),
+            nonce_managers: self.nonce_managers.clone(),
+        }
+    }
+
+    /// View of this connection whose queries read the state at the given block
+    ///
+    /// Only queries are affected: transactions are always built against the best block.
+    pub async fn at(&self, block: impl Into<BlockId>) -> Result<Self> {
+        let at = self.connection().at(block).await?.at;
+        Ok(Self { at, ..self.clone() })
+    }
+
+    /// Block queries read the state at, `None` for the best block
+    pub fn block(&self) -> Option<H256> {
+        self.at.as_ref().map(from_block_hash)
+    }
+
+    /// Account the connection signs for
     pub fn account_id(&self) -> AccountId32 {
         let acc_bytes: &[u8; 32] = self.inner.signer.account_id().as_ref();
         AccountId32::from(*acc_bytes)
     }
 
+    /// Free balance of the signer
     pub async fn get_balance(&self) -> Result<u128> {
         use selendra_client::pallets::system::SystemApi;
         let account_id = self.inner.signer.account_id();
-        Ok(self.inner.get_free_balance(account_id.clone(), None).await)
+        Ok(self.inner.get_free_balance(account_id.clone(), self.at).await)
     }
 
-    pub async fn get_account_info(&self) -> Result<crate::substrate::types::AccountInfo> {
-        use selendra_client::ConnectionApi;
-
-        let account_id = self.inner.signer.account_id();
-        
-        // Access the system.account storage using the same pattern as SystemApi in selendra_client
-        // The account() method expects a Static<AccountId32> type
-        let acc_bytes: &[u8; 32] = account_id.as_ref();
-        let converted_account = selendra_client::sp_core::crypto::AccountId32::from(*acc_bytes);
-        let addrs = selendra_client::api::storage().system().account(Static(converted_account));
-
-        let account_info = self.inner.get_storage_entry_maybe(&addrs, None)
-            .await
-            .ok_or_else(|| SDKError::Query("Account not found".to_string()))?;
-
-        // Convert from selendra_client AccountInfo to SDK AccountInfo
-        Ok(crate::substrate::types::AccountInfo {
-            nonce: account_info.nonce,
-            consumers: account_info.consumers,
-            providers: account_info.providers,
-            sufficients: account_info.sufficients,
-            data: crate::substrate::types::AccountData {
-                free: account_info.data.free,
-                reserved: account_info.data.reserved,
-                free_frozen: account_info.data.frozen,
-                reserved_frozen: account_info.data.frozen,
-                flags: account_info.data.flags.0,
-            },
-        })
+    /// Account information of the signer, with its locks and freezes
+    pub async fn get_account_info(&self) -> Result<AccountInfo> {
+        self.connection().get_account_info(&self.account_id()).await
     }
 
+    /// Underlying subxt client
+    pub(crate) fn client(&self) -> &selendra_client::SubxtClient {
+        self.inner.connection.as_client()
+    }
 
-    pub async fn transfer(&self, to: AccountId32, amount: u128) -> Result<TxHash> {
-        use selendra_client::pallets::balances::BalanceUserApi;
-        let to_bytes: &[u8; 32] = to.as_ref();
-        let to_account = selendra_client::AccountId::from(*to_bytes);
-        let tx_info = self.inner.transfer_keep_alive(to_account, amount, TxStatus::InBlock)
-            .await
-            .map_err(|e| SDKError::Transaction(format!("Failed: {}", e)))?;
-        Ok(tx_info.tx_hash)
+    /// Inclusion level transactions of this connection wait for
+    pub fn tx_status(&self) -> TxStatus {
+        self.tx_status
     }
 
-    pub async fn stake_bond(&self, amount: u128) -> Result<TxHash> {
-        use selendra_client::pallets::staking::StakingUserApi;
-        let tx_info = self.inner.bond(amount, TxStatus::InBlock)
-            .await
-            .map_err(|e| SDKError::Transaction(format!("Failed: {}", e)))?;
-        Ok(tx_info.tx_hash)
+    /// Copy of this connection whose transactions wait for the given inclusion level
+    ///
+    /// `TxStatus::Submitted` returns as soon as the node accepts the extrinsic,
+    /// `TxStatus::InBlock` (the default) once it is in a best block and
+    /// `TxStatus::Finalized` once that block is finalized.
+    pub fn with_tx_status(&self, tx_status: TxStatus) -> Self {
+        Self {
+            tx_status,
+            ..self.clone()
+        }
     }
 
-    pub async fn stake_nominate(&self, targets: Vec<AccountId32>) -> Result<TxHash> {
-        use selendra_client::pallets::staking::StakingUserApi;
-        use selendra_client::pallets::utility::UtilityApi;
+    /// Copy of this connection acting as a proxy of `real`
+    ///
+    /// Every call submitted or estimated through the returned connection is wrapped in
+    /// `Proxy::proxy`, so it dispatches with `real` as origin. `force_type` selects a
+    /// specific proxy type when the signer holds several for `real`.
+    pub fn proxied(&self, real: AccountId32, force_type: Option<ProxyType>) -> Self {
+        Self {
+            proxied: Some((real, force_type)),
+            ..self.clone()
+        }
+    }
 
-        // Convert targets to selendra_client AccountIds
-        let target_accounts: Vec<selendra_client::AccountId> = targets
-            .into_iter()
-            .map(|acc| {
-                let acc_bytes: &[u8; 32] = acc.as_ref();
-                selendra_client::AccountId::from(*acc_bytes)
-            })
-            .collect();
+    /// Account calls dispatch as: the proxied account, or the signer itself
+    pub fn origin(&self) -> AccountId32 {
+        match &self.proxied {
+            Some((real, _)) => real.clone(),
+            None => self.account_id(),
+        }
+    }
 
-        // If there's only one target, use the direct nominate method
-        if target_accounts.len() == 1 {
-            let tx_info = self.inner.nominate(target_accounts[0].clone(), TxStatus::InBlock)
-                .await
-                .map_err(|e| SDKError::Transaction(format!("Failed: {}", e)))?;
-            Ok(tx_info.tx_hash)
-        } else {
-            // For multiple targets, we need to create a batch call
-            // For now, just nominate the first one as the underlying API only supports single nominee
-            let tx_info = self.inner.nominate(target_accounts[0].clone(), TxStatus::InBlock)
-                .await
-                .map_err(|e| SDKError::Transaction(format!("Failed: {}", e)))?;
-            Ok(tx_info.tx_hash)
+    /// Wrap a call in `Proxy::proxy` when acting as a proxy
+    fn dispatched_call(&self, call: Call) -> Call {
+        match &self.proxied {
+            Some((real, force_type)) => Call::proxy(real, force_type.clone(), call),
+            None => call,
         }
     }
 
-    pub async fn stake_validate(&self, commission: u32) -> Result<TxHash> {
-        use selendra_client::pallets::staking::StakingUserApi;
-        // Convert commission from percentage (0-100) to u8
-        let commission_percentage = if commission > 100 {
-            100u8
-        } else {
-            commission as u8
+    /// Sign and submit a call, waiting for the connection's inclusion level
+    pub async fn submit(&self, call: Call) -> Result<TxResult> {
+        self.submit_with_status(call, self.tx_status).await
+    }
+
+    /// Nonce tracker of the signer, shared by every connection signing for it
+    pub fn nonce_manager(&self) -> &NonceManager {
+        &self.nonces
+    }
+
+    /// Sign and submit a call, waiting for the given inclusion level
+    ///
+    /// A dispatch failure is not an `Err`: the extrinsic was included and paid for,
+    /// and the decoded error is returned in `TxResult::dispatch_error`.
+    ///
+    /// Nonces come from the connection's `NonceManager`, so calls can be submitted
+    /// concurrently from clones of the same connection.
+    pub async fn submit_with_status(&self, call: Call, status: TxStatus) -> Result<TxResult> {
+        let call = self.dispatched_call(call);
+        let signer = PairSigner::new(self.inner.signer.signer().clone());
+        let submit_error = |e: subxt::Error| {
+            SDKError::Transaction(format!("Failed to submit {}::{}: {}", call.pallet(), call.name(), e))
         };
 
-        let tx_info = self.inner.validate(commission_percentage, TxStatus::InBlock)
+        let nonce = self.nonces.reserve(&self.connection(), &self.account_id()).await?;
+        let progress = self.client().tx()
+            .create_signed_with_nonce(&call.payload(), &signer, nonce.nonce().into(), Default::default())
+            .map_err(submit_error)?
+            .submit_and_watch()
             .await
-            .map_err(|e| SDKError::Transaction(format!("Failed: {}", e)))?;
-        Ok(tx_info.tx_hash)
+            .map_err(submit_error)?;
+        nonce.advance();
+
+        let result = self.connection().watch(progress, status).await;
+        if result.is_err() {
+            // The transaction may have been dropped or invalidated, leaving a gap
+            self.nonces.reset().await;
+        }
+        result
     }
 
-    pub async fn stake_bond_extra(&self, amount: u128) -> Result<TxHash> {
-        use selendra_client::pallets::staking::StakingUserApi;
-        let tx_info = self.inner.bond_extra_stake(amount, TxStatus::InBlock)
+    /// Sign a call with the next account nonce, returning the encoded extrinsic
+    pub(crate) async fn sign_call(&self, call: &Call) -> Result<Vec<u8>> {
+        let call = self.dispatched_call(call.clone());
+        let signer = PairSigner::new(self.inner.signer.signer().clone());
+        let extrinsic = self.client().tx()
+            .create_signed(&call.payload(), &signer, Default::default())
             .await
-            .map_err(|e| SDKError::Transaction(format!("Failed: {}", e)))?;
-        Ok(tx_info.tx_hash)
+            .map_err(|e| SDKError::Transaction(format!("Failed to sign {}::{}: {}", call.pallet(), call.name(), e)))?;
+        Ok(extrinsic.into_encoded())
     }
 
-    pub async fn stake_chill(&self) -> Result<TxHash> {
-        use selendra_client::pallets::staking::StakingUserApi;
-        let tx_info = self.inner.chill(TxStatus::InBlock)
-            .await
-            .map_err(|e| SDKError::Transaction(format!("Failed: {}", e)))?;
-        Ok(tx_info.tx_hash)
+    /// Transfer that keeps the sender above the existential deposit
+    pub async fn transfer(&self, to: AccountId32, amount: u128) -> Result<TxResult> {
+        self.submit(Call::transfer_keep_alive(&to, amount)).await
     }
 
-    pub async fn treasury_propose_spend(&self, value: u128, beneficiary: AccountId32) -> Result<TxHash> {
-        use selendra_client::pallets::treasury::TreasuryUserApi;
-        let benef_bytes: &[u8; 32] = beneficiary.as_ref();
-        let beneficiary_account = selendra_client::AccountId::from(*benef_bytes);
-        let tx_info = self.inner.propose_spend(value, beneficiary_account, TxStatus::InBlock)
-            .await
-            .map_err(|e| SDKError::Transaction(format!("Failed: {}", e)))?;
-        Ok(tx_info.tx_hash)
+    /// Transfer that may leave the sender below the existential deposit, reaping it
+    pub async fn transfer_allow_death(&self, to: AccountId32, amount: u128) -> Result<TxResult> {
+        self.submit(Call::transfer_allow_death(&to, amount)).await
+    }
+
+    /// Transfer the whole transferable balance, keeping the existential deposit
+    /// if `keep_alive`
+    pub async fn transfer_all(&self, to: AccountId32, keep_alive: bool) -> Result<TxResult> {
+        self.submit(Call::transfer_all(&to, keep_alive)).await
+    }
+
+    /// Transfer from any account through `Sudo::sudo`, for dev chains where the
+    /// signer is the sudo key
+    pub async fn force_transfer(&self, source: AccountId32, to: AccountId32, amount: u128) -> Result<TxResult> {
+        self.submit(Call::sudo(Call::force_transfer(&source, &to, amount))).await
+    }
+
+    /// Bond `amount` of the signer's balance for staking
+    pub async fn stake_bond(&self, amount: u128) -> Result<TxResult> {
+        self.submit(Call::stake_bond(amount)).await
+    }
+
+    /// Nominate validators, checked against `Staking::MaxNominations`
+    pub async fn stake_nominate(&self, targets: Vec<AccountId32>) -> Result<TxResult> {
+        check_nominations(&targets, self.connection().get_max_nominations().await?)?;
+        self.submit(Call::stake_nominate(&targets)).await
+    }
+
+    /// Declare the signer as a validator with the given commission
+    pub async fn stake_validate(&self, commission: u32) -> Result<TxResult> {
+        self.submit(Call::stake_validate(commission)).await
+    }
+
+    /// Add `amount` to the signer's bonded stake
+    pub async fn stake_bond_extra(&self, amount: u128) -> Result<TxResult> {
+        self.submit(Call::stake_bond_extra(amount)).await
+    }
+
+    /// Stop nominating or validating
+    pub async fn stake_chill(&self) -> Result<TxResult> {
+        self.submit(Call::stake_chill()).await
+    }
+
+    /// Propose a treasury spend of `value` to `beneficiary`
+    pub async fn treasury_propose_spend(&self, value: u128, beneficiary: AccountId32) -> Result<TxResult> {
+        self.submit(Call::treasury_propose_spend(value, &beneficiary)).await
     }
 }
 
+/// Check nomination targets against `Staking::MaxNominations`
+///
+/// Runtimes without the constant enforce their own nomination quota on submission.
+fn check_nominations(targets: &[AccountId32], max: Option<u32>) -> Result<()> {
+    if targets.is_empty() {
+        return Err(SDKError::Validation("At least one nomination target is required".to_string()));
+    }
+
+    match max {
+        Some(max) if targets.len() > max as usize => {
+            Err(SDKError::TooManyNominations { requested: targets.len(), max })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Sr25519 key pair with the seed it was derived from
 #[derive(Clone)]
 pub struct KeyPair {
+    /// Seed or secret uri the pair was derived from
     pub seed: String,
+    /// The key pair itself
     pub pair: sr25519::Pair,
 }
 
 impl KeyPair {
+    /// Account id of the public key
     pub fn account_id(&self) -> AccountId32 {
         self.pair.public().into()
     }
 
+    /// SS58 address of the public key
     pub fn to_ss58check(&self) -> String {
         self.account_id().to_ss58check()
     }
 }
 
+/// Key pair from a secret uri, or from `//<seed>` when it does not parse as one
 pub fn keypair_from_string(seed: &str) -> KeyPair {
     let pair = sr25519::Pair::from_string(seed, None)
         .unwrap_or_else(|_| {
@@ -295,4 +678,34 @@ mod tests {
         assert_eq!(kp1.account_id(), kp2.account_id());
         println!("✅ Deterministic keypair test passed");
     }
+
+    #[test]
+    fn test_nominate_call_keeps_all_targets() {
+        let targets: Vec<AccountId32> = (0..16u8).map(|i| AccountId32::from([i; 32])).collect();
+        let call = DecodedValue::from(Call::stake_nominate(&targets).into_value());
+
+        let nominate = call.at(0).unwrap();
+        assert_eq!(nominate.variant_name(), Some("nominate"));
+        let addresses = nominate.at(0).unwrap().items();
+        assert_eq!(addresses.len(), 16);
+        for (address, target) in addresses.iter().zip(&targets) {
+            assert_eq!(address.variant_name(), Some("Id"));
+            assert_eq!(address.at(0).and_then(|id| id.as_account_id()).as_ref(), Some(target));
+        }
+        println!("✅ Nominate call test passed");
+    }
+
+    #[test]
+    fn test_too_many_nominations() {
+        let targets: Vec<AccountId32> = (0..17u8).map(|i| AccountId32::from([i; 32])).collect();
+
+        let error = check_nominations(&targets, Some(16)).unwrap_err();
+        assert!(matches!(error, SDKError::TooManyNominations { requested: 17, max: 16 }));
+        assert_eq!(error.to_string(), "Too many nominations: 17 targets, at most 16 allowed");
+
+        assert!(check_nominations(&targets[..16], Some(16)).is_ok());
+        assert!(check_nominations(&targets, None).is_ok());
+        assert!(matches!(check_nominations(&[], Some(16)), Err(SDKError::Validation(_))));
+        println!("✅ Nomination quota test passed");
+    }
 }
@@ -1,13 +1,297 @@
 //! Smart contracts module - wrapper around selendra_client contracts
 
 use crate::types::{Result, SDKError};
-use sp_core::crypto::AccountId32;
-use selendra_client::{AccountId, TxStatus};
+use crate::substrate::{
+    calls::Call,
+    client::{Connection, SignedConnection},
+    types::{Balance, DispatchError, TxResult, Weight},
+};
+#[cfg(feature = "contracts")]
+use crate::substrate::events::EventDecoder;
+#[cfg(feature = "contracts")]
+use contract_transcode::{ContractMessageTranscoder, Value};
+use scale_codec::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use sp_core::{crypto::AccountId32, hashing::blake2_256, H256};
+use selendra_client::AccountId;
 use selendra_client::contract::{ContractInstance, ReadonlyCallParams, ExecCallParams};
 use selendra_client::ConnectionApi;
 
+/// Return flag set when a contract reverted its changes
+const REVERT_FLAG: u32 = 1;
+
+/// Storage deposit of a dry run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub enum StorageDeposit {
+    /// Deposit refunded to the caller
+    Refund(Balance),
+    /// Deposit charged from the caller
+    Charge(Balance),
+}
+
+impl StorageDeposit {
+    /// Storage deposit limit covering this deposit, `None` if nothing is charged
+    pub fn limit(&self) -> Option<Balance> {
+        match self {
+            StorageDeposit::Charge(amount) => Some(*amount),
+            StorageDeposit::Refund(_) => None,
+        }
+    }
+}
+
+/// Outcome of a contract dry run, from the `ContractsApi` runtime API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRun {
+    /// Gas used by the execution
+    pub gas_consumed: Weight,
+    /// Gas limit needed for the execution to succeed
+    pub gas_required: Weight,
+    /// Storage deposit charged or refunded
+    pub storage_deposit: StorageDeposit,
+    /// Debug output of the contract, empty unless the node enables it
+    pub debug_message: String,
+    /// Data returned by the contract
+    pub data: Vec<u8>,
+    /// Whether the contract reverted its changes
+    pub reverted: bool,
+    /// Address of the contract a constructor would instantiate
+    pub address: Option<AccountId32>,
+}
+
+/// `pallet_contracts::ContractResult`, without the trailing events of newer runtimes
+#[derive(Decode)]
+struct RawContractResult<R> {
+    gas_consumed: Weight,
+    gas_required: Weight,
+    storage_deposit: StorageDeposit,
+    debug_message: Vec<u8>,
+    result: std::result::Result<R, sp_runtime::DispatchError>,
+}
+
+/// `pallet_contracts::ExecReturnValue`
+#[derive(Decode)]
+struct RawExecReturnValue {
+    flags: u32,
+    data: Vec<u8>,
+}
+
+/// `pallet_contracts::InstantiateReturnValue`
+#[derive(Decode)]
+struct RawInstantiateReturnValue {
+    result: RawExecReturnValue,
+    account_id: [u8; 32],
+}
+
+/// `pallet_contracts::CodeUploadReturnValue`
+#[derive(Decode)]
+struct RawCodeUploadReturnValue {
+    _code_hash: H256,
+    deposit: Balance,
+}
+
+/// Code to instantiate a contract from
+#[derive(Debug, Clone, PartialEq, Eq, Encode)]
+pub enum ContractCode {
+    /// Wasm code uploaded along with the instantiation
+    Upload(Vec<u8>),
+    /// Hash of already uploaded code
+    Existing(H256),
+}
+
+/// Code uploaded with `Contract::upload_code`
+#[derive(Debug, Clone)]
+pub struct UploadedCode {
+    /// Hash to instantiate the code with
+    pub code_hash: H256,
+    /// Upload transaction, with its events
+    pub tx: TxResult,
+}
+
+/// Contract deployed with `Contract::instantiate` or `instantiate_with_code`
+pub struct InstantiatedContract {
+    /// The new contract
+    pub contract: Contract,
+    /// Instantiation transaction, with its events
+    pub tx: TxResult,
+}
+
+impl InstantiatedContract {
+    /// Address of the new contract
+    pub fn address(&self) -> AccountId32 {
+        self.contract.address()
+    }
+}
+
+/// Dry run of a contract message with its decoded return value
+#[cfg(feature = "contracts")]
+#[derive(Debug, Clone)]
+pub struct MessageDryRun {
+    /// Gas, storage deposit and raw output of the dry run
+    pub dry_run: DryRun,
+    /// Return value decoded with the message's return type from the metadata
+    ///
+    /// A message returning a `Result` reverts on `Err`, so a reverted dry run
+    /// still carries its error value here.
+    pub value: Value,
+}
+
+/// Gas and storage deposit limits of a contract call
+#[cfg(feature = "contracts")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CallLimits {
+    /// Limits taken from a dry run of the call
+    #[default]
+    Estimated,
+    /// Limits given by the caller
+    Explicit {
+        /// Gas limit
+        gas_limit: Weight,
+        /// Storage deposit limit, unlimited if `None`
+        storage_deposit_limit: Option<Balance>,
+    },
+}
+
+impl Connection {
+    /// Call a `ContractsApi` runtime API and decode its `ContractResult`
+    async fn contracts_api<R: Decode>(&self, method: &str, params: Vec<u8>) -> Result<(DryRun, R)> {
+        let bytes = self.client().rpc()
+            .state_call_raw(&format!("ContractsApi_{}", method), Some(&params), self.at_hash())
+            .await
+            .map_err(|e| SDKError::ContractError(format!("Dry run failed: {}", e)))?;
+        let raw = RawContractResult::<R>::decode(&mut &bytes[..])
+            .map_err(|e| SDKError::SerializationError(format!("Failed to decode dry run result: {}", e)))?;
+        let debug_message = String::from_utf8_lossy(&raw.debug_message).into_owned();
+
+        let result = raw.result.map_err(|error| {
+            let error = DispatchError::from_runtime(&error, &self.client().metadata());
+            SDKError::ContractError(format!("Dry run failed with {}: {}", error, debug_message))
+        })?;
+        let dry_run = DryRun {
+            gas_consumed: raw.gas_consumed,
+            gas_required: raw.gas_required,
+            storage_deposit: raw.storage_deposit,
+            debug_message,
+            data: Vec::new(),
+            reverted: false,
+            address: None,
+        };
+        Ok((dry_run, result))
+    }
+
+    /// Dry run the instantiation of a contract by `origin` with encoded constructor data
+    ///
+    /// Fails if the instantiation would fail; a reverting constructor is reported
+    /// in `DryRun::reverted`.
+    pub async fn dry_run_instantiate(
+        &self,
+        origin: &AccountId32,
+        value: Balance,
+        code: ContractCode,
+        data: Vec<u8>,
+        salt: Vec<u8>,
+    ) -> Result<DryRun> {
+        let params = (origin, value, None::<Weight>, None::<Balance>, code, data, salt).encode();
+        let (mut dry_run, instantiated) =
+            self.contracts_api::<RawInstantiateReturnValue>("instantiate", params).await?;
+        dry_run.data = instantiated.result.data;
+        dry_run.reverted = instantiated.result.flags & REVERT_FLAG != 0;
+        dry_run.address = Some(AccountId32::from(instantiated.account_id));
+        Ok(dry_run)
+    }
+
+    /// Dry run a call of `contract` by `origin` with encoded message data
+    ///
+    /// Fails if the call would fail; a reverting message is reported in
+    /// `DryRun::reverted`.
+    pub async fn dry_run_call(
+        &self,
+        origin: &AccountId32,
+        contract: &AccountId32,
+        value: Balance,
+        data: Vec<u8>,
+    ) -> Result<DryRun> {
+        let params = (origin, contract, value, None::<Weight>, None::<Balance>, data).encode();
+        let (mut dry_run, returned) = self.contracts_api::<RawExecReturnValue>("call", params).await?;
+        dry_run.reverted = returned.flags & REVERT_FLAG != 0;
+        dry_run.data = returned.data;
+        Ok(dry_run)
+    }
+
+    /// Storage deposit an upload of `code` would reserve
+    async fn dry_run_upload(&self, origin: &AccountId32, code: Vec<u8>) -> Result<Balance> {
+        // Arguments: origin, code, storage_deposit_limit, determinism
+        let params = (origin, code, None::<Balance>, 0u8).encode();
+        let bytes = self.client().rpc()
+            .state_call_raw("ContractsApi_upload_code", Some(&params), self.at_hash())
+            .await
+            .map_err(|e| SDKError::ContractError(format!("Dry run failed: {}", e)))?;
+        let result = std::result::Result::<RawCodeUploadReturnValue, sp_runtime::DispatchError>::decode(&mut &bytes[..])
+            .map_err(|e| SDKError::SerializationError(format!("Failed to decode dry run result: {}", e)))?;
+        result
+            .map(|uploaded| uploaded.deposit)
+            .map_err(|error| {
+                let error = DispatchError::from_runtime(&error, &self.client().metadata());
+                SDKError::ContractError(format!("Code upload dry run failed with {}", error))
+            })
+    }
+}
+
+/// Code hash declared in a contract metadata file, if any
+fn metadata_code_hash(metadata_path: &str) -> Result<Option<H256>> {
+    let metadata: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(metadata_path)?)?;
+    match metadata.pointer("/source/hash").and_then(|hash| hash.as_str()) {
+        Some(hash) => hash.trim_start_matches("0x")
+            .parse()
+            .map(Some)
+            .map_err(|e| SDKError::ContractError(format!("Invalid code hash in metadata: {}", e))),
+        None => Ok(None),
+    }
+}
+
+/// Hash of `wasm`, failing if it does not match the hash declared in the metadata file
+fn checked_code_hash(wasm: &[u8], metadata_path: &str) -> Result<H256> {
+    let code_hash = H256::from(blake2_256(wasm));
+    if let Some(expected) = metadata_code_hash(metadata_path)? {
+        if expected != code_hash {
+            return Err(SDKError::Validation(format!(
+                "Code hash {:?} does not match {:?} from the metadata",
+                code_hash, expected
+            )));
+        }
+    }
+    Ok(code_hash)
+}
+
+/// Fail if a dry run reverted
+#[cfg(feature = "contracts")]
+fn check_reverted(dry_run: &DryRun, what: &str) -> Result<()> {
+    if dry_run.reverted {
+        return Err(SDKError::ContractError(format!(
+            "{} reverted with 0x{}: {}",
+            what,
+            hex::encode(&dry_run.data),
+            dry_run.debug_message
+        )));
+    }
+    Ok(())
+}
+
+/// Decode the return data of an ink! message, unwrapping its `MessageResult`
+#[cfg(feature = "contracts")]
+fn decode_message_result<T: Decode>(message: &str, data: &[u8]) -> Result<T> {
+    // `Err` holds an `ink::LangError`, raised before the message ran
+    match std::result::Result::<T, u8>::decode(&mut &data[..]) {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(_)) => Err(SDKError::ContractError(format!("{} could not read its input", message))),
+        Err(e) => Err(SDKError::SerializationError(format!("Failed to decode {} result: {}", message, e))),
+    }
+}
+
+/// Deployed ink! contract
 pub struct Contract {
     inner: ContractInstance,
+    #[cfg(feature = "contracts")]
+    transcoder: ContractMessageTranscoder,
 }
 
 impl Contract {
@@ -17,7 +301,11 @@ impl Contract {
         let addr = AccountId::from(*addr_bytes);
         let inner = ContractInstance::new(addr, metadata_path)
             .map_err(|e| SDKError::ContractError(format!("Failed to load contract: {}", e)))?;
-        Ok(Self { inner })
+        Ok(Self {
+            inner,
+            #[cfg(feature = "contracts")]
+            transcoder: load_transcoder(metadata_path)?,
+        })
     }
 
     /// Get contract address
@@ -26,6 +314,205 @@ impl Contract {
         AccountId32::from(*addr_bytes)
     }
 
+    /// Upload contract code so it can be instantiated by hash
+    ///
+    /// The storage deposit limit is taken from a dry run. Fails if the code does
+    /// not match the hash declared in the metadata file, or if the upload fails.
+    pub async fn upload_code(conn: &SignedConnection, wasm: &[u8], metadata_path: &str) -> Result<UploadedCode> {
+        let code_hash = checked_code_hash(wasm, metadata_path)?;
+        let deposit = conn.connection().dry_run_upload(&conn.origin(), wasm.to_vec()).await?;
+        let tx = conn.submit(Call::contract_upload_code(wasm.to_vec(), Some(deposit))).await?;
+        if let Some(error) = &tx.dispatch_error {
+            return Err(SDKError::ContractError(format!("Code upload failed with {}", error)));
+        }
+        Ok(UploadedCode { code_hash, tx })
+    }
+
+    /// Instantiate uploaded code, calling `constructor` with `args`
+    ///
+    /// Gas and storage deposit limits are taken from a dry run, which also
+    /// reports a failing or reverting constructor before anything is submitted.
+    #[cfg(feature = "contracts")]
+    pub async fn instantiate(
+        conn: &SignedConnection,
+        metadata_path: &str,
+        code_hash: H256,
+        constructor: &str,
+        args: &[String],
+        value: Balance,
+        salt: Vec<u8>,
+    ) -> Result<InstantiatedContract> {
+        let data = encode_constructor(metadata_path, constructor, args)?;
+        let dry_run = conn.connection()
+            .dry_run_instantiate(&conn.origin(), value, ContractCode::Existing(code_hash), data.clone(), salt.clone())
+            .await?;
+        check_reverted(&dry_run, constructor)?;
+
+        let call = Call::contract_instantiate(
+            value,
+            dry_run.gas_required,
+            dry_run.storage_deposit.limit(),
+            code_hash,
+            data,
+            salt,
+        );
+        Self::instantiated(conn, call, metadata_path).await
+    }
+
+    /// Upload code and instantiate it in one transaction, calling `constructor` with `args`
+    ///
+    /// Gas and storage deposit limits are taken from a dry run, which also
+    /// reports a failing or reverting constructor before anything is submitted.
+    #[cfg(feature = "contracts")]
+    pub async fn instantiate_with_code(
+        conn: &SignedConnection,
+        metadata_path: &str,
+        wasm: &[u8],
+        constructor: &str,
+        args: &[String],
+        value: Balance,
+        salt: Vec<u8>,
+    ) -> Result<InstantiatedContract> {
+        let data = encode_constructor(metadata_path, constructor, args)?;
+        let dry_run = conn.connection()
+            .dry_run_instantiate(&conn.origin(), value, ContractCode::Upload(wasm.to_vec()), data.clone(), salt.clone())
+            .await?;
+        check_reverted(&dry_run, constructor)?;
+
+        let call = Call::contract_instantiate_with_code(
+            value,
+            dry_run.gas_required,
+            dry_run.storage_deposit.limit(),
+            wasm.to_vec(),
+            data,
+            salt,
+        );
+        Self::instantiated(conn, call, metadata_path).await
+    }
+
+    /// Submit an instantiation and load the contract from its `Instantiated` event
+    #[cfg(feature = "contracts")]
+    async fn instantiated(conn: &SignedConnection, call: Call, metadata_path: &str) -> Result<InstantiatedContract> {
+        let tx = conn.submit(call).await?;
+        if let Some(error) = &tx.dispatch_error {
+            return Err(SDKError::ContractError(format!("Instantiation failed with {}", error)));
+        }
+
+        let decoder = EventDecoder::from_connection(&conn.connection());
+        let address = match tx.find_events("Contracts", "Instantiated").last() {
+            Some(raw) => decoder.decode(raw)?.field("contract").and_then(|contract| contract.as_account_id()),
+            None => None,
+        }
+        .ok_or_else(|| SDKError::ContractError("Instantiated event not found".to_string()))?;
+
+        Ok(InstantiatedContract {
+            contract: Contract::new(address, metadata_path)?,
+            tx,
+        })
+    }
+
+    /// Encode a message selector and its arguments
+    #[cfg(feature = "contracts")]
+    pub fn encode(&self, message: &str, args: &[String]) -> Result<Vec<u8>> {
+        self.transcoder
+            .encode(message, args)
+            .map_err(|e| SDKError::ContractError(format!("Failed to encode {}: {}", message, e)))
+    }
+
+    /// Build the `Contracts::call` of a message, e.g. to estimate its fee
+    #[cfg(feature = "contracts")]
+    pub fn exec_call(
+        &self,
+        message: &str,
+        args: &[String],
+        value: u128,
+        gas_limit: Weight,
+        storage_deposit_limit: Option<u128>,
+    ) -> Result<Call> {
+        let data = self.encode(message, args)?;
+        Ok(Call::contract_call(&self.address(), value, gas_limit, storage_deposit_limit, data))
+    }
+
+    /// Dry run a message from `origin`, returning gas, storage deposit and the decoded return value
+    #[cfg(feature = "contracts")]
+    pub async fn dry_run(
+        &self,
+        conn: &Connection,
+        origin: &AccountId32,
+        message: &str,
+        args: &[String],
+        value: Balance,
+    ) -> Result<MessageDryRun> {
+        let data = self.encode(message, args)?;
+        let dry_run = conn.dry_run_call(origin, &self.address(), value, data).await?;
+        Ok(MessageDryRun {
+            value: self.decode_return(message, &dry_run.data)?,
+            dry_run,
+        })
+    }
+
+    /// Decode the return data of a message with its return type from the metadata
+    #[cfg(feature = "contracts")]
+    pub fn decode_return(&self, message: &str, data: &[u8]) -> Result<Value> {
+        self.transcoder
+            .decode_return(message, &mut &data[..])
+            .map_err(|e| SDKError::SerializationError(format!("Failed to decode {} result: {}", message, e)))
+    }
+
+    /// Read a message's return value as a value tree, dry running it from `origin`
+    #[cfg(feature = "contracts")]
+    pub async fn read_value(
+        &self,
+        conn: &Connection,
+        origin: &AccountId32,
+        message: &str,
+        args: &[String],
+    ) -> Result<Value> {
+        Ok(self.dry_run(conn, origin, message, args, 0).await?.value)
+    }
+
+    /// Read a message's return value as `T`, dry running it from `origin`
+    ///
+    /// `T` is the message's declared return type, e.g. `Result<u128, MyError>` for
+    /// a message returning a `Result`.
+    #[cfg(feature = "contracts")]
+    pub async fn read_typed<T: Decode>(
+        &self,
+        conn: &Connection,
+        origin: &AccountId32,
+        message: &str,
+        args: &[String],
+    ) -> Result<T> {
+        let data = self.encode(message, args)?;
+        let dry_run = conn.dry_run_call(origin, &self.address(), 0, data).await?;
+        decode_message_result(message, &dry_run.data)
+    }
+
+    /// Execute a message with explicit or estimated gas and storage deposit limits
+    ///
+    /// With `CallLimits::Estimated` the message is dry run first, and a message
+    /// that would revert fails before anything is submitted.
+    #[cfg(feature = "contracts")]
+    pub async fn exec_with_limits(
+        &self,
+        conn: &SignedConnection,
+        message: &str,
+        args: &[String],
+        value: Balance,
+        limits: CallLimits,
+    ) -> Result<TxResult> {
+        let (gas_limit, storage_deposit_limit) = match limits {
+            CallLimits::Explicit { gas_limit, storage_deposit_limit } => (gas_limit, storage_deposit_limit),
+            CallLimits::Estimated => {
+                let data = self.encode(message, args)?;
+                let dry_run = conn.connection().dry_run_call(&conn.origin(), &self.address(), value, data).await?;
+                check_reverted(&dry_run, message)?;
+                (dry_run.gas_required, dry_run.storage_deposit.limit())
+            }
+        };
+        conn.submit(self.exec_call(message, args, value, gas_limit, storage_deposit_limit)?).await
+    }
+
     /// Read-only contract call (no gas, no transaction)
     pub async fn read<C: ConnectionApi>(
         &self,
@@ -43,6 +530,7 @@ impl Contract {
     }
 
     /// Execute contract call (submits transaction)
+    #[deprecated(note = "use `Contract::exec_with_limits`, which sets explicit or estimated gas and storage deposit limits")]
     pub async fn exec<C: selendra_client::SignedConnectionApi>(
         &self,
         conn: &C,
@@ -58,3 +546,103 @@ impl Contract {
         Ok(tx_info.tx_hash)
     }
 }
+
+/// Load the message transcoder of a contract metadata file
+#[cfg(feature = "contracts")]
+fn load_transcoder(metadata_path: &str) -> Result<ContractMessageTranscoder> {
+    ContractMessageTranscoder::load(metadata_path)
+        .map_err(|e| SDKError::ContractError(format!("Failed to load contract metadata: {}", e)))
+}
+
+/// Encode a constructor selector and its arguments
+#[cfg(feature = "contracts")]
+fn encode_constructor(metadata_path: &str, constructor: &str, args: &[String]) -> Result<Vec<u8>> {
+    load_transcoder(metadata_path)?
+        .encode(constructor, args)
+        .map_err(|e| SDKError::ContractError(format!("Failed to encode {}: {}", constructor, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instantiate_dry_run_decoding() {
+        let weight = Weight::new(1_000, 200);
+        let result: std::result::Result<(u32, Vec<u8>, [u8; 32]), sp_runtime::DispatchError> =
+            Ok((REVERT_FLAG, vec![1, 2], [7u8; 32]));
+        let mut bytes = (weight, weight, StorageDeposit::Charge(50), b"panicked".to_vec(), result).encode();
+        // Newer runtimes append the events of the dry run
+        bytes.push(0);
+
+        let raw = RawContractResult::<RawInstantiateReturnValue>::decode(&mut &bytes[..]).unwrap();
+        assert_eq!(raw.gas_required, weight);
+        assert_eq!(raw.storage_deposit.limit(), Some(50));
+        let instantiated = raw.result.ok().unwrap();
+        assert_eq!(instantiated.result.flags & REVERT_FLAG, REVERT_FLAG);
+        assert_eq!(instantiated.account_id, [7u8; 32]);
+        assert_eq!(StorageDeposit::Refund(10).limit(), None);
+        println!("✅ Instantiate dry run decoding test passed");
+    }
+
+    #[test]
+    #[cfg(feature = "contracts")]
+    fn test_estimated_limits_revert() {
+        let mut dry_run = DryRun {
+            gas_consumed: Weight::new(800, 100),
+            gas_required: Weight::new(1_000, 200),
+            storage_deposit: StorageDeposit::Charge(0),
+            debug_message: "insufficient balance".to_string(),
+            data: vec![1, 2],
+            reverted: true,
+            address: None,
+        };
+
+        match check_reverted(&dry_run, "transfer") {
+            Err(SDKError::ContractError(message)) => {
+                assert_eq!(message, "transfer reverted with 0x0102: insufficient balance");
+            }
+            other => panic!("expected a revert error, got {:?}", other),
+        }
+
+        dry_run.reverted = false;
+        assert!(check_reverted(&dry_run, "transfer").is_ok());
+        println!("✅ Estimated limits revert test passed");
+    }
+
+    #[test]
+    fn test_code_hash_from_metadata() {
+        let wasm = b"\0asm\x01\0\0\0";
+        let metadata = |hash: H256| {
+            let mut file = tempfile::NamedTempFile::new().unwrap();
+            let json = serde_json::json!({ "source": { "hash": format!("{:?}", hash), "language": "ink! 4.3.0" } });
+            std::io::Write::write_all(&mut file, json.to_string().as_bytes()).unwrap();
+            file
+        };
+
+        let matching = metadata(H256::from(blake2_256(wasm)));
+        let path = matching.path().to_str().unwrap();
+        assert_eq!(checked_code_hash(wasm, path).unwrap(), H256::from(blake2_256(wasm)));
+
+        let other = metadata(H256::repeat_byte(1));
+        let path = other.path().to_str().unwrap();
+        assert!(matches!(checked_code_hash(wasm, path), Err(SDKError::Validation(_))));
+        println!("✅ Metadata code hash test passed");
+    }
+
+    #[test]
+    #[cfg(feature = "contracts")]
+    fn test_decode_message_result() {
+        let returned: std::result::Result<std::result::Result<u128, u8>, u8> = Ok(Err(3));
+        let value = decode_message_result::<std::result::Result<u128, u8>>("transfer", &returned.encode()).unwrap();
+        assert_eq!(value, Err(3));
+
+        let lang_error: std::result::Result<u128, u8> = Err(1);
+        assert!(matches!(
+            decode_message_result::<u128>("balance_of", &lang_error.encode()),
+            Err(SDKError::ContractError(_))
+        ));
+        assert!(decode_message_result::<u128>("balance_of", &[0, 1]).is_err());
+        println!("✅ Message result decoding test passed");
+    }
+}
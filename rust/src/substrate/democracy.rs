@@ -0,0 +1,481 @@
+//! Public proposals, referenda and voting backed by `pallet_democracy`
+
+use crate::substrate::calls::Call;
+use crate::substrate::client::{account_value, Connection, SignedConnection};
+use crate::substrate::types::{Balance, TxResult};
+use crate::substrate::value::{bounded_items, DecodedValue};
+use crate::types::{Result, SDKError};
+use serde::{Deserialize, Serialize};
+use sp_core::H256;
+use sp_runtime::AccountId32;
+use subxt::dynamic::Value;
+
+/// Identifier of the balance lock placed by democracy votes
+pub const DEMOCRACY_LOCK_ID: [u8; 8] = *b"democrac";
+
+/// Vote multiplier in exchange for locking the voted balance longer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Conviction {
+    /// 0.1x votes, no lock after the referendum
+    #[default]
+    None,
+    /// 1x votes, locked for one enactment period
+    Locked1x,
+    /// 2x votes, locked for 2 enactment periods
+    Locked2x,
+    /// 3x votes, locked for 4 enactment periods
+    Locked3x,
+    /// 4x votes, locked for 8 enactment periods
+    Locked4x,
+    /// 5x votes, locked for 16 enactment periods
+    Locked5x,
+    /// 6x votes, locked for 32 enactment periods
+    Locked6x,
+}
+
+impl Conviction {
+    const ALL: [Conviction; 7] = [
+        Conviction::None,
+        Conviction::Locked1x,
+        Conviction::Locked2x,
+        Conviction::Locked3x,
+        Conviction::Locked4x,
+        Conviction::Locked5x,
+        Conviction::Locked6x,
+    ];
+
+    /// Variant name of the conviction in the runtime
+    pub fn name(&self) -> &'static str {
+        match self {
+            Conviction::None => "None",
+            Conviction::Locked1x => "Locked1x",
+            Conviction::Locked2x => "Locked2x",
+            Conviction::Locked3x => "Locked3x",
+            Conviction::Locked4x => "Locked4x",
+            Conviction::Locked5x => "Locked5x",
+            Conviction::Locked6x => "Locked6x",
+        }
+    }
+
+    fn from_index(index: u8) -> Option<Self> {
+        Self::ALL.get(index as usize).copied()
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|conviction| conviction.name() == name)
+    }
+
+    /// Enactment periods the voted balance stays locked after the referendum
+    pub fn lock_periods(&self) -> u32 {
+        match self {
+            Conviction::None => 0,
+            conviction => 1 << (*conviction as u32 - 1),
+        }
+    }
+
+    /// Votes counted for `balance` locked with this conviction
+    pub fn votes(&self, balance: Balance) -> Balance {
+        match self {
+            Conviction::None => balance / 10,
+            conviction => balance.saturating_mul(*conviction as u128),
+        }
+    }
+
+    /// Dynamic value of the conviction, for building runtime calls
+    pub(crate) fn to_value(self) -> Value {
+        Value::unnamed_variant(self.name(), [])
+    }
+}
+
+/// Vote of an account on a referendum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AccountVote {
+    /// Aye or nay with the whole balance at a conviction
+    Standard {
+        /// Whether the vote is in favour
+        aye: bool,
+        /// Conviction of the vote
+        conviction: Conviction,
+        /// Balance voted
+        balance: Balance,
+    },
+    /// Balance split between aye and nay, without conviction
+    Split {
+        /// Balance voting aye
+        aye: Balance,
+        /// Balance voting nay
+        nay: Balance,
+    },
+}
+
+impl AccountVote {
+    /// Dynamic value of the vote, for building runtime calls
+    pub(crate) fn to_value(self) -> Value {
+        match self {
+            AccountVote::Standard { aye, conviction, balance } => {
+                // `Vote` encodes as one byte: the aye flag and the conviction index
+                let vote = ((aye as u8) << 7) | conviction as u8;
+                Value::named_variant(
+                    "Standard",
+                    [
+                        ("vote", Value::unnamed_composite([Value::u128(vote as u128)])),
+                        ("balance", Value::u128(balance)),
+                    ],
+                )
+            }
+            AccountVote::Split { aye, nay } => Value::named_variant(
+                "Split",
+                [("aye", Value::u128(aye)), ("nay", Value::u128(nay))],
+            ),
+        }
+    }
+
+    fn from_value(value: &DecodedValue) -> Option<Self> {
+        match value.variant_name()? {
+            "Standard" => {
+                let vote = value.field("vote")?.as_u128()? as u8;
+                Some(AccountVote::Standard {
+                    aye: vote & 0x80 != 0,
+                    conviction: Conviction::from_index(vote & 0x7f)?,
+                    balance: value.field("balance")?.as_u128()?,
+                })
+            }
+            "Split" => Some(AccountVote::Split {
+                aye: value.field("aye")?.as_u128()?,
+                nay: value.field("nay")?.as_u128()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Public proposal awaiting a referendum, from `Democracy::PublicProps`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicProposal {
+    /// Proposal index
+    pub index: u32,
+    /// Account that made the proposal
+    pub proposer: AccountId32,
+    /// Hash of the proposed call
+    pub proposal_hash: H256,
+    /// Deposit locked by each seconder
+    pub deposit: Balance,
+    /// Accounts that backed the proposal, starting with the proposer
+    pub seconds: Vec<AccountId32>,
+}
+
+/// Hash of a `Bounded<Call>`, hashing inline calls
+fn bounded_call_hash(value: &DecodedValue) -> Option<H256> {
+    let hash = match value.variant_name() {
+        Some("Inline") => return Some(H256(sp_core::hashing::blake2_256(&value.at(0)?.as_bytes()?))),
+        // `Legacy` and `Lookup` carry the hash first, older runtimes store a bare hash
+        Some(_) => value.at(0)?.as_bytes()?,
+        None => value.as_bytes()?,
+    };
+    (hash.len() == 32).then(|| H256::from_slice(&hash))
+}
+
+/// Votes of an ongoing referendum
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tally {
+    /// Conviction-weighted aye votes
+    pub ayes: Balance,
+    /// Conviction-weighted nay votes
+    pub nays: Balance,
+    /// Balance that voted, without conviction
+    pub turnout: Balance,
+}
+
+/// State of a referendum
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReferendumInfo {
+    /// Voting is open
+    Ongoing {
+        /// Block at which voting ends
+        end: u32,
+        /// Hash of the proposed call
+        proposal_hash: Option<H256>,
+        /// Threshold name: `SuperMajorityApprove`, `SuperMajorityAgainst` or `SimpleMajority`
+        threshold: String,
+        /// Blocks between approval and enactment
+        delay: u32,
+        /// Current votes
+        tally: Tally,
+    },
+    /// Voting has ended
+    Finished {
+        /// Whether the referendum passed
+        approved: bool,
+        /// Block at which voting ended
+        end: u32,
+    },
+}
+
+/// Referendum, from `Democracy::ReferendumInfoOf`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Referendum {
+    /// Referendum index
+    pub index: u32,
+    /// State of the referendum
+    pub info: ReferendumInfo,
+}
+
+impl Referendum {
+    fn from_value(index: u32, value: &DecodedValue) -> Option<Self> {
+        let info = match value.variant_name()? {
+            "Ongoing" => {
+                let status = value.at(0)?;
+                let tally = status.field("tally")?;
+                ReferendumInfo::Ongoing {
+                    end: status.field("end")?.as_u128()? as u32,
+                    proposal_hash: status.field("proposal")
+                        .or_else(|| status.field("proposal_hash"))
+                        .and_then(bounded_call_hash),
+                    threshold: status.field("threshold")?.variant_name()?.to_string(),
+                    delay: status.field("delay")?.as_u128()? as u32,
+                    tally: Tally {
+                        ayes: tally.field("ayes")?.as_u128()?,
+                        nays: tally.field("nays")?.as_u128()?,
+                        turnout: tally.field("turnout")?.as_u128()?,
+                    },
+                }
+            }
+            "Finished" => ReferendumInfo::Finished {
+                approved: value.field("approved")?.as_bool()?,
+                end: value.field("end")?.as_u128()? as u32,
+            },
+            _ => return None,
+        };
+        Some(Self { index, info })
+    }
+
+    /// Whether voting is still open
+    pub fn is_ongoing(&self) -> bool {
+        matches!(self.info, ReferendumInfo::Ongoing { .. })
+    }
+}
+
+/// Delegation of an account's voting power
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Delegation {
+    /// Account voting on behalf of the delegator
+    pub target: AccountId32,
+    /// Conviction of the delegated votes
+    pub conviction: Conviction,
+    /// Balance delegated
+    pub balance: Balance,
+}
+
+/// Voting state of an account, from `Democracy::VotingOf` and its balance lock
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VotingState {
+    /// Direct votes by referendum index, empty while delegating
+    pub votes: Vec<(u32, AccountVote)>,
+    /// Delegation of the account's votes, if any
+    pub delegating: Option<Delegation>,
+    /// Conviction-weighted votes delegated to the account
+    pub delegated_votes: Balance,
+    /// Balance delegated to the account
+    pub delegated_capital: Balance,
+    /// Lock left from removed votes, as the block it expires at and its amount
+    pub prior_lock: Option<(u32, Balance)>,
+    /// Amount currently locked by democracy on the account's balance
+    pub locked: Balance,
+}
+
+impl VotingState {
+    fn from_value(value: &DecodedValue) -> Option<Self> {
+        let mut state = VotingState::default();
+        if let Some(delegations) = value.field("delegations") {
+            state.delegated_votes = delegations.field("votes")?.as_u128()?;
+            state.delegated_capital = delegations.field("capital")?.as_u128()?;
+        }
+        if let Some(prior) = value.field("prior") {
+            let (block, amount) = (prior.at(0)?.as_u128()? as u32, prior.at(1)?.as_u128()?);
+            state.prior_lock = (amount > 0).then_some((block, amount));
+        }
+
+        match value.variant_name()? {
+            "Direct" => {
+                state.votes = bounded_items(value.field("votes")?)
+                    .into_iter()
+                    .filter_map(|vote| Some((vote.at(0)?.as_u128()? as u32, AccountVote::from_value(vote.at(1)?)?)))
+                    .collect();
+            }
+            "Delegating" => {
+                state.delegating = Some(Delegation {
+                    target: value.field("target")?.as_account_id()?,
+                    conviction: Conviction::from_name(value.field("conviction")?.variant_name()?)?,
+                    balance: value.field("balance")?.as_u128()?,
+                });
+            }
+            _ => return None,
+        }
+        Some(state)
+    }
+}
+
+impl Connection {
+    /// Public proposals awaiting a referendum, with their deposits
+    pub async fn get_public_proposals(&self) -> Result<Vec<PublicProposal>> {
+        let props = self.storage_fetch_or_default("Democracy", "PublicProps", vec![]).await?;
+        let props: Vec<_> = bounded_items(&props)
+            .into_iter()
+            .filter_map(|prop| {
+                Some((
+                    prop.at(0)?.as_u128()? as u32,
+                    bounded_call_hash(prop.at(1)?)?,
+                    prop.at(2)?.as_account_id()?,
+                ))
+            })
+            .collect();
+
+        let deposits = self
+            .storage_fetch_many(
+                "Democracy",
+                "DepositOf",
+                props.iter().map(|(index, _, _)| vec![Value::u128(*index as u128)]).collect(),
+            )
+            .await?;
+
+        Ok(props
+            .into_iter()
+            .zip(deposits)
+            .map(|((index, proposal_hash, proposer), deposit)| PublicProposal {
+                index,
+                proposer,
+                proposal_hash,
+                deposit: deposit.as_ref().and_then(|deposit| deposit.at(1)?.as_u128()).unwrap_or(0),
+                seconds: deposit.as_ref()
+                    .and_then(|deposit| deposit.at(0))
+                    .map(|seconds| seconds.as_account_ids())
+                    .unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    /// A referendum, `None` if it does not exist
+    pub async fn get_referendum(&self, index: u32) -> Result<Option<Referendum>> {
+        match self.storage_fetch("Democracy", "ReferendumInfoOf", vec![Value::u128(index as u128)]).await? {
+            Some(info) => Referendum::from_value(index, &info)
+                .map(Some)
+                .ok_or_else(|| SDKError::Query("Invalid ReferendumInfoOf storage value".to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Referenda with voting still open, with their tallies
+    pub async fn get_ongoing_referenda(&self) -> Result<Vec<Referendum>> {
+        let count = |value: DecodedValue| value.as_u128().unwrap_or(0) as u32;
+        let lowest = count(self.storage_fetch_or_default("Democracy", "LowestUnbaked", vec![]).await?);
+        let next = count(self.storage_fetch_or_default("Democracy", "ReferendumCount", vec![]).await?);
+
+        let indices: Vec<u32> = (lowest..next).collect();
+        let infos = self
+            .storage_fetch_many(
+                "Democracy",
+                "ReferendumInfoOf",
+                indices.iter().map(|index| vec![Value::u128(*index as u128)]).collect(),
+            )
+            .await?;
+
+        Ok(indices
+            .into_iter()
+            .zip(infos)
+            .filter_map(|(index, info)| Referendum::from_value(index, &info?))
+            .filter(Referendum::is_ongoing)
+            .collect())
+    }
+
+    /// Votes, delegations and democracy lock of an account
+    pub async fn get_voting_state(&self, account: &AccountId32) -> Result<VotingState> {
+        let voting = self.storage_fetch_or_default("Democracy", "VotingOf", vec![account_value(account)]).await?;
+        let mut state = VotingState::from_value(&voting)
+            .ok_or_else(|| SDKError::Query("Invalid VotingOf storage value".to_string()))?;
+
+        state.locked = self.get_balance_locks(account)
+            .await?
+            .into_iter()
+            .filter(|lock| lock.id == DEMOCRACY_LOCK_ID)
+            .map(|lock| lock.amount)
+            .sum();
+        Ok(state)
+    }
+}
+
+impl SignedConnection {
+    /// Vote on a referendum, replacing any previous vote on it
+    pub async fn democracy_vote(&self, ref_index: u32, vote: AccountVote) -> Result<TxResult> {
+        self.submit(Call::democracy_vote(ref_index, vote)).await
+    }
+
+    /// Delegate the voting power of `balance` to another account
+    pub async fn democracy_delegate(&self, to: AccountId32, conviction: Conviction, balance: Balance) -> Result<TxResult> {
+        self.submit(Call::democracy_delegate(&to, conviction, balance)).await
+    }
+
+    /// End the current delegation
+    pub async fn democracy_undelegate(&self) -> Result<TxResult> {
+        self.submit(Call::democracy_undelegate()).await
+    }
+
+    /// Remove the vote on a referendum, keeping any conviction lock it earned
+    pub async fn democracy_remove_vote(&self, ref_index: u32) -> Result<TxResult> {
+        self.submit(Call::democracy_remove_vote(ref_index)).await
+    }
+
+    /// Remove the expired democracy locks of `target`
+    pub async fn democracy_unlock(&self, target: AccountId32) -> Result<TxResult> {
+        self.submit(Call::democracy_unlock(&target)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::substrate::value::test_util::named;
+
+    fn variant(name: &str, fields: DecodedValue) -> DecodedValue {
+        DecodedValue::Variant(name.to_string(), Box::new(fields))
+    }
+
+    #[test]
+    fn test_conviction() {
+        assert_eq!(Conviction::None.votes(100), 10);
+        assert_eq!(Conviction::Locked3x.votes(100), 300);
+        assert_eq!(Conviction::None.lock_periods(), 0);
+        assert_eq!(Conviction::Locked6x.lock_periods(), 32);
+        assert_eq!(Conviction::from_name("Locked2x"), Some(Conviction::Locked2x));
+        println!("✅ Conviction test passed");
+    }
+
+    #[test]
+    fn test_voting_state_from_value() {
+        let standard = variant("Standard", named(vec![
+            ("vote", DecodedValue::Unnamed(vec![DecodedValue::U128(0x80 | 2)])),
+            ("balance", DecodedValue::U128(1_000)),
+        ]));
+        let split = variant("Split", named(vec![
+            ("aye", DecodedValue::U128(30)),
+            ("nay", DecodedValue::U128(70)),
+        ]));
+        let votes = DecodedValue::Unnamed(vec![DecodedValue::Unnamed(vec![
+            DecodedValue::Unnamed(vec![DecodedValue::U128(4), standard]),
+            DecodedValue::Unnamed(vec![DecodedValue::U128(5), split]),
+        ])]);
+        let voting = variant("Direct", named(vec![
+            ("votes", votes),
+            ("delegations", named(vec![("votes", DecodedValue::U128(0)), ("capital", DecodedValue::U128(0))])),
+            ("prior", DecodedValue::Unnamed(vec![DecodedValue::U128(0), DecodedValue::U128(0)])),
+        ]));
+
+        let state = VotingState::from_value(&voting).unwrap();
+        assert_eq!(state.votes, vec![
+            (4, AccountVote::Standard { aye: true, conviction: Conviction::Locked2x, balance: 1_000 }),
+            (5, AccountVote::Split { aye: 30, nay: 70 }),
+        ]);
+        assert_eq!(state.delegating, None);
+        assert_eq!(state.prior_lock, None);
+        println!("✅ Voting state decoding test passed");
+    }
+}
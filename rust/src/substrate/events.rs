@@ -1,11 +1,25 @@
 //! Event handling for Substrate chains
 
+use crate::substrate::client::{from_block_hash, Connection};
+use crate::substrate::value::DecodedValue;
 use crate::types::{Result, SDKError};
+use futures::{future, stream, Stream, StreamExt};
+use scale_codec::Decode;
 use sp_core::H256;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
+use std::pin::Pin;
+use subxt::events::{EventDetails, Phase};
+
+/// Stream of on-chain events produced by an attached listener
+pub type EventStream = Pin<Box<dyn Stream<Item = Result<RawEvent>> + Send>>;
+
+/// Stream of decoded on-chain events produced by an attached listener
+pub type DecodedEventStream = Pin<Box<dyn Stream<Item = Result<DecodedEvent>> + Send>>;
 
 /// Event manager
+#[derive(Default)]
 pub struct EventManager {
     filters: HashMap<String, EventFilter>,
 }
@@ -35,7 +49,7 @@ impl EventManager {
 }
 
 /// Event filter
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct EventFilter {
     /// Pallet name
     pub pallet: Option<String>,
@@ -72,6 +86,23 @@ impl EventFilter {
         self.parameters.insert(key, value);
         self
     }
+
+    /// Check if a raw event matches the pallet and event name of this filter
+    pub fn matches_raw(&self, raw: &RawEvent) -> bool {
+        if let Some(ref pallet) = self.pallet {
+            if raw.pallet != *pallet {
+                return false;
+            }
+        }
+
+        if let Some(ref event_name) = self.event_name {
+            if raw.name != *event_name {
+                return false;
+            }
+        }
+
+        true
+    }
 }
 
 /// Raw event data
@@ -87,6 +118,43 @@ pub struct RawEvent {
     pub phase: EventPhase,
     /// Topics
     pub topics: Vec<H256>,
+    /// Number of the block that emitted the event
+    #[serde(default)]
+    pub block_number: Option<u64>,
+    /// Hash of the block that emitted the event
+    #[serde(default)]
+    pub block_hash: Option<H256>,
+}
+
+impl RawEvent {
+    /// Index of the extrinsic that emitted the event, if any
+    pub fn extrinsic_index(&self) -> Option<u32> {
+        match self.phase {
+            EventPhase::ApplyExtrinsic(index) => Some(index),
+            _ => None,
+        }
+    }
+}
+
+/// Build a raw event from an event fetched from the node
+pub(crate) fn raw_event_from_details<T: subxt::Config>(
+    details: &EventDetails<T>,
+    block_number: Option<u64>,
+    block_hash: Option<H256>,
+) -> RawEvent {
+    RawEvent {
+        pallet: details.pallet_name().to_string(),
+        name: details.variant_name().to_string(),
+        data: details.field_bytes().to_vec(),
+        phase: match details.phase() {
+            Phase::ApplyExtrinsic(index) => EventPhase::ApplyExtrinsic(index),
+            Phase::Finalization => EventPhase::Finalization,
+            Phase::Initialization => EventPhase::Initialization,
+        },
+        topics: details.topics().iter().map(|topic| H256::from_slice(topic.as_ref())).collect(),
+        block_number,
+        block_hash,
+    }
 }
 
 /// Event phase information
@@ -101,8 +169,10 @@ pub enum EventPhase {
 }
 
 /// Event decoder
+#[derive(Clone, Default)]
 pub struct EventDecoder {
     pallets: HashMap<String, PalletMetadata>,
+    metadata: Option<subxt::Metadata>,
 }
 
 impl EventDecoder {
@@ -110,29 +180,95 @@ impl EventDecoder {
     pub fn new() -> Self {
         Self {
             pallets: HashMap::new(),
+            metadata: None,
         }
     }
 
+    /// Create a decoder backed by runtime metadata
+    pub fn from_metadata(metadata: subxt::Metadata) -> Self {
+        Self {
+            pallets: HashMap::new(),
+            metadata: Some(metadata),
+        }
+    }
+
+    /// Create a decoder from the runtime metadata of a connected node
+    pub fn from_connection(conn: &Connection) -> Self {
+        Self::from_metadata(conn.client().metadata())
+    }
+
+    /// Create a decoder from a SCALE encoded `.scale` metadata file
+    pub fn from_metadata_file(path: impl AsRef<Path>) -> Result<Self> {
+        let bytes = std::fs::read(path)?;
+        let metadata = subxt::Metadata::decode(&mut &bytes[..])
+            .map_err(|e| SDKError::SerializationError(format!("Invalid metadata file: {}", e)))?;
+        Ok(Self::from_metadata(metadata))
+    }
+
+    /// Runtime metadata backing the decoder, if any
+    pub fn metadata(&self) -> Option<&subxt::Metadata> {
+        self.metadata.as_ref()
+    }
+
     /// Add pallet metadata
     pub fn add_pallet(&mut self, pallet: String, metadata: PalletMetadata) {
         self.pallets.insert(pallet, metadata);
     }
 
     /// Decode raw event
+    ///
+    /// Pallets added with [`EventDecoder::add_pallet`] take precedence over the
+    /// runtime metadata and are decoded without field values.
     pub fn decode(&self, raw: &RawEvent) -> Result<DecodedEvent> {
-        let pallet_metadata = self.pallets.get(&raw.pallet)
-            .ok_or_else(|| SDKError::Unknown(format!("Pallet '{}' not found", raw.pallet)))?;
+        if let Some(pallet_metadata) = self.pallets.get(&raw.pallet) {
+            let event_metadata = pallet_metadata.events.get(&raw.name)
+                .ok_or_else(|| SDKError::Unknown(format!("Event '{}' not found in pallet '{}'", raw.name, raw.pallet)))?;
+
+            return Ok(DecodedEvent::new(
+                raw.pallet.clone(),
+                raw.name.clone(),
+                raw.data.clone(),
+                raw.phase.clone(),
+                raw.topics.clone(),
+                event_metadata.event_type.clone(),
+            ));
+        }
 
-        let event_metadata = pallet_metadata.events.get(&raw.name)
+        let metadata = self.metadata.as_ref()
+            .ok_or_else(|| SDKError::Unknown(format!("Pallet '{}' not found", raw.pallet)))?;
+        let pallet = metadata.pallet_by_name(&raw.pallet)
+            .ok_or_else(|| SDKError::Unknown(format!("Pallet '{}' not found", raw.pallet)))?;
+        let variant = pallet.event_variants()
+            .and_then(|variants| variants.iter().find(|variant| variant.name == raw.name))
             .ok_or_else(|| SDKError::Unknown(format!("Event '{}' not found in pallet '{}'", raw.name, raw.pallet)))?;
 
+        let mut cursor = &raw.data[..];
+        let mut fields = Vec::with_capacity(variant.fields.len());
+        let mut encoded_fields = Vec::with_capacity(variant.fields.len());
+        for (index, field) in variant.fields.iter().enumerate() {
+            let start = cursor;
+            let value = DecodedValue::decode(&mut cursor, field.ty.id, metadata)?;
+            let name = field.name.clone().unwrap_or_else(|| index.to_string());
+            encoded_fields.push((name.clone(), start[..start.len() - cursor.len()].to_vec()));
+            fields.push((name, value));
+        }
+
+        let named = variant.fields.iter().all(|field| field.name.is_some());
+        let fields = if named {
+            DecodedValue::Named(fields)
+        } else {
+            DecodedValue::Unnamed(fields.into_iter().map(|(_, value)| value).collect())
+        };
+
         Ok(DecodedEvent {
             pallet: raw.pallet.clone(),
             name: raw.name.clone(),
             data: raw.data.clone(),
             phase: raw.phase.clone(),
             topics: raw.topics.clone(),
-            event_type: event_metadata.event_type.clone(),
+            event_type: format!("{}::{}", raw.pallet, raw.name),
+            fields,
+            encoded_fields,
         })
     }
 }
@@ -140,21 +276,27 @@ impl EventDecoder {
 /// Pallet metadata
 #[derive(Debug, Clone)]
 pub struct PalletMetadata {
+    /// Events of the pallet by name
     pub events: HashMap<String, EventMetadata>,
 }
 
 /// Event metadata
 #[derive(Debug, Clone)]
 pub struct EventMetadata {
+    /// Event name
     pub event_type: String,
+    /// Fields of the event
     pub fields: Vec<EventField>,
 }
 
 /// Event field
 #[derive(Debug, Clone)]
 pub struct EventField {
+    /// Field name
     pub name: String,
+    /// Type name of the field
     pub field_type: String,
+    /// Whether the field is indexed
     pub indexed: bool,
 }
 
@@ -173,6 +315,12 @@ pub struct DecodedEvent {
     pub topics: Vec<H256>,
     /// Event type
     pub event_type: String,
+    /// Field values decoded against the runtime metadata
+    #[serde(default)]
+    pub fields: DecodedValue,
+    /// SCALE encoded bytes of each field, keyed by field name or position
+    #[serde(default)]
+    pub encoded_fields: Vec<(String, Vec<u8>)>,
 }
 
 impl DecodedEvent {
@@ -192,9 +340,26 @@ impl DecodedEvent {
             phase,
             topics,
             event_type,
+            fields: DecodedValue::default(),
+            encoded_fields: Vec::new(),
         }
     }
 
+    /// Get a decoded field by name
+    pub fn field(&self, name: &str) -> Option<&DecodedValue> {
+        self.fields.field(name)
+    }
+
+    /// Render the event and its fields as plain JSON
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "pallet": self.pallet,
+            "name": self.name,
+            "phase": self.phase,
+            "fields": self.fields.to_json(),
+        })
+    }
+
     /// Get event identifier
     pub fn id(&self) -> String {
         format!("{}::{}", self.pallet, self.name)
@@ -214,10 +379,12 @@ impl DecodedEvent {
             }
         }
 
-        // Check parameter filters
-        for (_key, _expected_value) in &filter.parameters {
-            // This is a simplified check - in reality you'd need to decode the event data
-            // and compare against the expected values
+        // Parameter filters hold the SCALE encoding of the expected field value
+        for (key, expected_value) in &filter.parameters {
+            match self.encoded_fields.iter().find(|(name, _)| name == key) {
+                Some((_, encoded)) if encoded == expected_value => {}
+                _ => return false,
+            }
         }
 
         true
@@ -225,9 +392,11 @@ impl DecodedEvent {
 }
 
 /// Event listener
+#[derive(Default)]
 pub struct EventListener {
     manager: EventManager,
     decoder: EventDecoder,
+    finalized_only: bool,
 }
 
 impl EventListener {
@@ -236,9 +405,22 @@ impl EventListener {
         Self {
             manager: EventManager::new(),
             decoder: EventDecoder::new(),
+            finalized_only: false,
         }
     }
 
+    /// Use a specific decoder instead of the runtime metadata of the attached node
+    pub fn with_decoder(mut self, decoder: EventDecoder) -> Self {
+        self.decoder = decoder;
+        self
+    }
+
+    /// Only follow finalized blocks instead of the best chain
+    pub fn finalized_only(mut self, finalized_only: bool) -> Self {
+        self.finalized_only = finalized_only;
+        self
+    }
+
     /// Add event filter
     pub fn add_filter(&mut self, name: String, filter: EventFilter) {
         self.manager.add_filter(name, filter);
@@ -257,4 +439,219 @@ impl EventListener {
 
         Ok(matched_events)
     }
-}
\ No newline at end of file
+
+    /// Attach the listener to a node and stream the events of every new block
+    ///
+    /// Events are emitted in block order. When filters are registered, only events
+    /// matching at least one filter are emitted. Events checked against a filter with
+    /// parameters are decoded as in [`EventListener::attach_decoded`].
+    pub async fn attach(&self, conn: &Connection) -> Result<EventStream> {
+        let decoder = self.attached_decoder(conn);
+        let filters: Vec<EventFilter> = self.manager.get_filters().values().cloned().collect();
+
+        let events = self.subscribe(conn)
+            .await?
+            .filter_map(move |raw| {
+                future::ready(match raw {
+                    Ok(raw) => match matches_any(&filters, &decoder, &raw) {
+                        Ok(true) => Some(Ok(raw)),
+                        Ok(false) => None,
+                        Err(e) => Some(Err(e)),
+                    },
+                    Err(e) => Some(Err(e)),
+                })
+            });
+
+        Ok(Box::pin(events))
+    }
+
+    /// Attach the listener to a node and stream decoded events of every new block
+    ///
+    /// Events are decoded with the listener's decoder, or with the runtime metadata
+    /// of the node when no metadata was provided. Parameter filters are applied to
+    /// the decoded fields.
+    pub async fn attach_decoded(&self, conn: &Connection) -> Result<DecodedEventStream> {
+        let decoder = self.attached_decoder(conn);
+        let filters: Vec<EventFilter> = self.manager.get_filters().values().cloned().collect();
+
+        let events = self.subscribe(conn)
+            .await?
+            .map(move |raw| raw.and_then(|raw| decoder.decode(&raw)))
+            .filter(move |decoded| {
+                future::ready(match decoded {
+                    Ok(event) => filters.is_empty() || filters.iter().any(|filter| event.matches(filter)),
+                    Err(_) => true,
+                })
+            });
+
+        Ok(Box::pin(events))
+    }
+
+    /// Decoder of the listener, backed by the runtime metadata of the node if it has none
+    fn attached_decoder(&self, conn: &Connection) -> EventDecoder {
+        if self.decoder.metadata.is_some() {
+            self.decoder.clone()
+        } else {
+            EventDecoder {
+                metadata: Some(conn.client().metadata()),
+                ..self.decoder.clone()
+            }
+        }
+    }
+
+    /// Events of every new block whose pallet and name match at least one filter
+    async fn subscribe(&self, conn: &Connection) -> Result<EventStream> {
+        let filters: Vec<EventFilter> = self.manager.get_filters().values().cloned().collect();
+
+        let blocks = if self.finalized_only {
+            conn.client().blocks().subscribe_finalized().await
+        } else {
+            conn.client().blocks().subscribe_best().await
+        }
+        .map_err(|e| SDKError::Connection(format!("Failed to subscribe to blocks: {}", e)))?;
+
+        let events = blocks
+            .then(move |block| {
+                let filters = filters.clone();
+                async move {
+                    let block = block
+                        .map_err(|e| SDKError::Query(format!("Block subscription failed: {}", e)))?;
+                    let block_number = block.number() as u64;
+                    let block_hash = from_block_hash(&block.hash());
+                    let events = block.events()
+                        .await
+                        .map_err(|e| SDKError::Query(format!("Failed to fetch events: {}", e)))?;
+
+                    let mut matched = Vec::new();
+                    for details in events.iter() {
+                        let details = details
+                            .map_err(|e| SDKError::Query(format!("Failed to decode event: {}", e)))?;
+                        let raw = raw_event_from_details(&details, Some(block_number), Some(block_hash));
+                        if filters.is_empty() || filters.iter().any(|filter| filter.matches_raw(&raw)) {
+                            matched.push(raw);
+                        }
+                    }
+
+                    Ok::<_, SDKError>(matched)
+                }
+            })
+            .flat_map(|result| match result {
+                Ok(matched) => stream::iter(matched.into_iter().map(Ok).collect::<Vec<_>>()),
+                Err(e) => stream::iter(vec![Err(e)]),
+            });
+
+        Ok(Box::pin(events))
+    }
+}
+
+/// Whether a raw event matches at least one filter
+///
+/// The event is only decoded when a filter with parameters matches its pallet and name.
+fn matches_any(filters: &[EventFilter], decoder: &EventDecoder, raw: &RawEvent) -> Result<bool> {
+    if filters.is_empty() {
+        return Ok(true);
+    }
+
+    let mut decoded = None;
+    for filter in filters.iter().filter(|filter| filter.matches_raw(raw)) {
+        if filter.parameters.is_empty() {
+            return Ok(true);
+        }
+        let event = match &decoded {
+            Some(event) => event,
+            None => decoded.insert(decoder.decode(raw)?),
+        };
+        if event.matches(filter) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::substrate::value::test_util;
+    use scale_codec::Encode;
+
+    fn transfer_event() -> DecodedEvent {
+        let mut event = DecodedEvent::new(
+            "Balances".to_string(),
+            "Transfer".to_string(),
+            Vec::new(),
+            EventPhase::ApplyExtrinsic(1),
+            Vec::new(),
+            "Balances::Transfer".to_string(),
+        );
+        event.encoded_fields = vec![
+            ("from".to_string(), [1u8; 32].encode()),
+            ("to".to_string(), [2u8; 32].encode()),
+            ("amount".to_string(), 500u128.encode()),
+        ];
+        event
+    }
+
+    #[test]
+    fn test_filter_parameters() {
+        let event = transfer_event();
+
+        let to_bob = EventFilter::new()
+            .pallet("Balances".to_string())
+            .parameter("to".to_string(), [2u8; 32].encode());
+        assert!(event.matches(&to_bob));
+
+        let other_amount = EventFilter::new().parameter("amount".to_string(), 1u128.encode());
+        assert!(!event.matches(&other_amount));
+
+        let unknown_field = EventFilter::new().parameter("fee".to_string(), 0u128.encode());
+        assert!(!event.matches(&unknown_field));
+        println!("✅ Parameter filter test passed");
+    }
+
+    #[test]
+    fn test_filter_decoded_fields() {
+        #[derive(scale_info::TypeInfo)]
+        #[allow(dead_code)]
+        enum BalancesEvent {
+            Transfer { from: [u8; 32], to: [u8; 32], amount: u128 },
+        }
+
+        let metadata = test_util::metadata(vec![test_util::pallet(
+            "Balances",
+            5,
+            Some(scale_info::meta_type::<BalancesEvent>()),
+            None,
+        )]);
+        let raw = RawEvent {
+            pallet: "Balances".to_string(),
+            name: "Transfer".to_string(),
+            data: ([1u8; 32], [2u8; 32], 500u128).encode(),
+            phase: EventPhase::ApplyExtrinsic(1),
+            topics: Vec::new(),
+            block_number: Some(10),
+            block_hash: None,
+        };
+
+        let event = EventDecoder::from_metadata(metadata.clone()).decode(&raw).unwrap();
+        assert_eq!(event.field("amount").and_then(|amount| amount.as_u128()), Some(500));
+
+        let to_bob = EventFilter::new()
+            .event_name("Transfer".to_string())
+            .parameter("to".to_string(), [2u8; 32].encode())
+            .parameter("amount".to_string(), 500u128.encode());
+        assert!(event.matches(&to_bob));
+
+        let to_charlie = EventFilter::new().parameter("to".to_string(), [3u8; 32].encode());
+        assert!(!event.matches(&to_charlie));
+
+        // attach applies the same parameter filters to raw events
+        let decoder = EventDecoder::from_metadata(metadata);
+        assert!(matches_any(&[to_charlie.clone(), to_bob], &decoder, &raw).unwrap());
+        assert!(!matches_any(std::slice::from_ref(&to_charlie), &decoder, &raw).unwrap());
+        // Events are only decoded for filters with parameters
+        let transfers = EventFilter::new().event_name("Transfer".to_string());
+        assert!(matches_any(&[transfers], &EventDecoder::new(), &raw).unwrap());
+        assert!(matches_any(&[to_charlie], &EventDecoder::new(), &raw).is_err());
+        println!("✅ Decoded field filter test passed");
+    }
+}
@@ -0,0 +1,116 @@
+//! Fee estimation through the `TransactionPaymentApi` runtime API
+
+use crate::substrate::calls::Call;
+use crate::substrate::client::SignedConnection;
+use crate::substrate::types::{Balance, DispatchClass, FeeEstimate, Weight};
+use crate::types::{Result, SDKError};
+use scale_codec::{Decode, Encode};
+
+/// `pallet_transaction_payment::RuntimeDispatchInfo`
+#[derive(Decode)]
+struct RuntimeDispatchInfo {
+    weight: Weight,
+    class: DispatchClass,
+    partial_fee: Balance,
+}
+
+/// `pallet_transaction_payment::InclusionFee`
+#[derive(Decode)]
+struct InclusionFee {
+    base_fee: Balance,
+    len_fee: Balance,
+    adjusted_weight_fee: Balance,
+}
+
+/// `pallet_transaction_payment::FeeDetails`, whose tip is not encoded
+#[derive(Decode)]
+struct FeeDetails {
+    inclusion_fee: Option<InclusionFee>,
+}
+
+impl FeeEstimate {
+    /// Whether an account with the given transferable balance can pay the fee and `amount`
+    pub fn is_affordable(&self, transferable: Balance, amount: Balance) -> bool {
+        transferable
+            .checked_sub(self.partial_fee)
+            .is_some_and(|remaining| remaining >= amount)
+    }
+}
+
+impl SignedConnection {
+    /// Estimate the fee of a call signed by this connection, without submitting it
+    ///
+    /// The call is signed with the current nonce so the estimate accounts for the
+    /// real encoded length. Works for any `Call`, e.g. `Call::transfer_keep_alive`,
+    /// `Call::stake_bond` or `Call::contract_call`.
+    pub async fn estimate_fee(&self, call: &Call) -> Result<FeeEstimate> {
+        let extrinsic = self.sign_call(call).await?;
+        let mut params = extrinsic.clone();
+        (extrinsic.len() as u32).encode_to(&mut params);
+
+        let runtime_api = self.client().runtime_api().at_latest()
+            .await
+            .map_err(|e| SDKError::Query(format!("Failed to access runtime API: {}", e)))?;
+
+        let info: RuntimeDispatchInfo = runtime_api
+            .call_raw("TransactionPaymentApi_query_info", Some(&params))
+            .await
+            .map_err(|e| SDKError::Query(format!("Failed to query fee info: {}", e)))?;
+        let details: FeeDetails = runtime_api
+            .call_raw("TransactionPaymentApi_query_fee_details", Some(&params))
+            .await
+            .map_err(|e| SDKError::Query(format!("Failed to query fee details: {}", e)))?;
+
+        // Unsigned and mandatory extrinsics pay no inclusion fee
+        let fee = details.inclusion_fee.unwrap_or(InclusionFee {
+            base_fee: 0,
+            len_fee: 0,
+            adjusted_weight_fee: 0,
+        });
+
+        Ok(FeeEstimate {
+            weight: info.weight,
+            class: info.class,
+            partial_fee: info.partial_fee,
+            base_fee: fee.base_fee,
+            len_fee: fee.len_fee,
+            adjusted_weight_fee: fee.adjusted_weight_fee,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_fee_details() {
+        let mut bytes = Some((1u128, 2u128, 3u128)).encode();
+        let details = FeeDetails::decode(&mut bytes.as_slice()).unwrap();
+        let fee = details.inclusion_fee.unwrap();
+        assert_eq!((fee.base_fee, fee.len_fee, fee.adjusted_weight_fee), (1, 2, 3));
+
+        bytes = (Weight::new(1_000, 64), DispatchClass::Normal, 6u128).encode();
+        let info = RuntimeDispatchInfo::decode(&mut bytes.as_slice()).unwrap();
+        assert_eq!(info.weight, Weight::new(1_000, 64));
+        assert_eq!(info.class, DispatchClass::Normal);
+        assert_eq!(info.partial_fee, 6);
+        println!("✅ Fee details decoding test passed");
+    }
+
+    #[test]
+    fn test_fee_affordable() {
+        let estimate = FeeEstimate {
+            weight: Weight::default(),
+            class: DispatchClass::Normal,
+            partial_fee: 10,
+            base_fee: 5,
+            len_fee: 2,
+            adjusted_weight_fee: 3,
+        };
+        assert!(estimate.is_affordable(100, 90));
+        assert!(!estimate.is_affordable(100, 91));
+        assert!(!estimate.is_affordable(5, 0));
+        println!("✅ Fee affordability test passed");
+    }
+}
@@ -0,0 +1,390 @@
+//! On-chain identities backed by `pallet_identity`
+
+use crate::substrate::calls::{option_value, Call};
+use crate::substrate::client::{account_value, Connection, SignedConnection};
+use crate::substrate::types::{Balance, TxResult};
+use crate::substrate::value::{bounded_items, DecodedValue};
+use crate::types::{Result, SDKError};
+use serde::{Deserialize, Serialize};
+use sp_runtime::AccountId32;
+use std::collections::HashMap;
+use subxt::dynamic::Value;
+
+/// Longest text an identity field can hold
+pub const MAX_IDENTITY_FIELD_LEN: usize = 32;
+
+/// Dynamic value of an identity `Data` field holding `text`, `Data::None` if empty
+pub(crate) fn data_value(text: Option<&str>) -> Result<Value> {
+    match text.filter(|text| !text.is_empty()) {
+        None => Ok(Value::unnamed_variant("None", [])),
+        Some(text) if text.len() > MAX_IDENTITY_FIELD_LEN => Err(SDKError::Validation(format!(
+            "Identity field \"{}\" is longer than {} bytes",
+            text, MAX_IDENTITY_FIELD_LEN
+        ))),
+        Some(text) => Ok(Value::unnamed_variant(format!("Raw{}", text.len()), [Value::from_bytes(text)])),
+    }
+}
+
+/// Text of a `Data` value, `None` for empty or hashed data
+fn data_text(value: &DecodedValue) -> Option<String> {
+    if !value.variant_name()?.starts_with("Raw") {
+        return None;
+    }
+    let bytes = value.at(0)?.as_bytes()?;
+    (!bytes.is_empty()).then(|| String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Identity fields, from `pallet_identity::IdentityInfo`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdentityInfo {
+    /// Display name
+    pub display: Option<String>,
+    /// Legal name
+    pub legal: Option<String>,
+    /// Website
+    pub web: Option<String>,
+    /// Matrix handle
+    pub riot: Option<String>,
+    /// Email address
+    pub email: Option<String>,
+    /// Twitter handle
+    pub twitter: Option<String>,
+    /// Image, usually a hash of it
+    pub image: Option<String>,
+    /// PGP key fingerprint
+    pub pgp_fingerprint: Option<[u8; 20]>,
+}
+
+impl IdentityInfo {
+    fn from_value(value: &DecodedValue) -> Self {
+        let text = |name: &str| value.field(name).and_then(data_text);
+        Self {
+            display: text("display"),
+            legal: text("legal"),
+            web: text("web"),
+            riot: text("riot"),
+            email: text("email"),
+            twitter: text("twitter"),
+            image: text("image"),
+            pgp_fingerprint: value.field("pgp_fingerprint")
+                .and_then(|fingerprint| fingerprint.as_option())
+                .and_then(|fingerprint| fingerprint.as_bytes())
+                .and_then(|fingerprint| fingerprint.try_into().ok()),
+        }
+    }
+
+    /// Dynamic value of the identity, for `set_identity`
+    pub(crate) fn to_value(&self) -> Result<Value> {
+        Ok(Value::named_composite([
+            ("additional", Value::unnamed_composite([])),
+            ("display", data_value(self.display.as_deref())?),
+            ("legal", data_value(self.legal.as_deref())?),
+            ("web", data_value(self.web.as_deref())?),
+            ("riot", data_value(self.riot.as_deref())?),
+            ("email", data_value(self.email.as_deref())?),
+            ("pgp_fingerprint", option_value(self.pgp_fingerprint.map(Value::from_bytes))),
+            ("image", data_value(self.image.as_deref())?),
+            ("twitter", data_value(self.twitter.as_deref())?),
+        ]))
+    }
+}
+
+/// Registrar's judgement of an identity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Judgement {
+    /// No judgement yet
+    Unknown,
+    /// Judgement requested, with the fee reserved for the registrar
+    FeePaid(Balance),
+    /// Information looks reasonable but was not checked in depth
+    Reasonable,
+    /// Information was checked and is correct
+    KnownGood,
+    /// Information used to be correct but is outdated
+    OutOfDate,
+    /// Information is low quality or imprecise
+    LowQuality,
+    /// Information is wrong
+    Erroneous,
+}
+
+impl Judgement {
+    fn from_value(value: &DecodedValue) -> Option<Self> {
+        Some(match value.variant_name()? {
+            "Unknown" => Judgement::Unknown,
+            "FeePaid" => Judgement::FeePaid(value.at(0)?.as_u128()?),
+            "Reasonable" => Judgement::Reasonable,
+            "KnownGood" => Judgement::KnownGood,
+            "OutOfDate" => Judgement::OutOfDate,
+            "LowQuality" => Judgement::LowQuality,
+            "Erroneous" => Judgement::Erroneous,
+            _ => return None,
+        })
+    }
+}
+
+/// Registered identity of an account, from `Identity::IdentityOf`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Identity {
+    /// Identity fields
+    pub info: IdentityInfo,
+    /// Judgements by registrar index
+    pub judgements: Vec<(u32, Judgement)>,
+    /// Deposit reserved for the identity
+    pub deposit: Balance,
+}
+
+impl Identity {
+    fn from_value(value: &DecodedValue) -> Option<Self> {
+        // Newer runtimes store the registration together with a username
+        let registration = if value.field("info").is_some() { value } else { value.at(0)? };
+        Some(Self {
+            info: IdentityInfo::from_value(registration.field("info")?),
+            judgements: bounded_items(registration.field("judgements")?)
+                .into_iter()
+                .filter_map(|judgement| Some((judgement.at(0)?.as_u128()? as u32, Judgement::from_value(judgement.at(1)?)?)))
+                .collect(),
+            deposit: registration.field("deposit")?.as_u128()?,
+        })
+    }
+
+    /// Whether a registrar judged the identity `Reasonable` or `KnownGood`
+    pub fn is_verified(&self) -> bool {
+        self.judgements
+            .iter()
+            .any(|(_, judgement)| matches!(judgement, Judgement::Reasonable | Judgement::KnownGood))
+    }
+}
+
+/// Sub-identity link of an account, from `Identity::SuperOf`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubIdentity {
+    /// Account holding the parent identity
+    pub parent: AccountId32,
+    /// Name of the sub-identity
+    pub name: Option<String>,
+}
+
+impl SubIdentity {
+    fn from_value(value: &DecodedValue) -> Option<Self> {
+        Some(Self {
+            parent: value.at(0)?.as_account_id()?,
+            name: value.at(1).and_then(data_text),
+        })
+    }
+}
+
+/// Identity registrar, from `Identity::Registrars`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Registrar {
+    /// Registrar index, used with `request_judgement`
+    pub index: u32,
+    /// Registrar account
+    pub account: AccountId32,
+    /// Fee charged for a judgement
+    pub fee: Balance,
+}
+
+impl Connection {
+    /// Identity registered for an account, `None` if it has none
+    pub async fn identity_of(&self, account: &AccountId32) -> Result<Option<Identity>> {
+        match self.storage_fetch("Identity", "IdentityOf", vec![account_value(account)]).await? {
+            Some(identity) => Identity::from_value(&identity)
+                .map(Some)
+                .ok_or_else(|| SDKError::Query("Invalid IdentityOf storage value".to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Parent identity of a sub-account, `None` if it is not a sub-identity
+    pub async fn super_of(&self, account: &AccountId32) -> Result<Option<SubIdentity>> {
+        Ok(self.storage_fetch("Identity", "SuperOf", vec![account_value(account)])
+            .await?
+            .and_then(|sub| SubIdentity::from_value(&sub)))
+    }
+
+    /// Sub-accounts of an identity with their names
+    pub async fn subs_of(&self, account: &AccountId32) -> Result<Vec<(AccountId32, Option<String>)>> {
+        let subs = self.storage_fetch_or_default("Identity", "SubsOf", vec![account_value(account)]).await?;
+        let subs = subs.at(1).map(|subs| subs.as_account_ids()).unwrap_or_default();
+        let names = self
+            .storage_fetch_many("Identity", "SuperOf", subs.iter().map(|sub| vec![account_value(sub)]).collect())
+            .await?;
+
+        Ok(subs
+            .into_iter()
+            .zip(names)
+            .map(|(sub, name)| (sub, name.and_then(|name| SubIdentity::from_value(&name)).and_then(|sub| sub.name)))
+            .collect())
+    }
+
+    /// Registrars that can judge identities
+    pub async fn get_registrars(&self) -> Result<Vec<Registrar>> {
+        let registrars = self.storage_fetch_or_default("Identity", "Registrars", vec![]).await?;
+        Ok(bounded_items(&registrars)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, registrar)| {
+                let registrar = registrar.as_option()?;
+                Some(Registrar {
+                    index: index as u32,
+                    account: registrar.field("account")?.as_account_id()?,
+                    fee: registrar.field("fee")?.as_u128()?,
+                })
+            })
+            .collect())
+    }
+
+    /// Display names of many accounts, batched into at most three storage queries
+    ///
+    /// Sub-accounts resolve to `parent/sub` names. Accounts without a display
+    /// name are left out of the map.
+    pub async fn resolve_display_names(&self, accounts: &[AccountId32]) -> Result<HashMap<AccountId32, String>> {
+        let keys = |accounts: &[AccountId32]| -> Vec<Vec<Value>> {
+            accounts.iter().map(|account| vec![account_value(account)]).collect()
+        };
+        let display = |identity: Option<DecodedValue>| {
+            identity.and_then(|identity| Identity::from_value(&identity)).and_then(|identity| identity.info.display)
+        };
+
+        let identities = self.storage_fetch_many("Identity", "IdentityOf", keys(accounts)).await?;
+        let mut names = HashMap::new();
+        let mut unnamed = Vec::new();
+        for (account, identity) in accounts.iter().zip(identities) {
+            match display(identity) {
+                Some(name) => {
+                    names.insert(account.clone(), name);
+                }
+                None => unnamed.push(account.clone()),
+            }
+        }
+        if unnamed.is_empty() {
+            return Ok(names);
+        }
+
+        let subs: Vec<(AccountId32, SubIdentity)> = unnamed
+            .iter()
+            .cloned()
+            .zip(self.storage_fetch_many("Identity", "SuperOf", keys(&unnamed)).await?)
+            .filter_map(|(account, sub)| Some((account, SubIdentity::from_value(&sub?)?)))
+            .collect();
+        if subs.is_empty() {
+            return Ok(names);
+        }
+
+        let parents: Vec<AccountId32> = subs.iter().map(|(_, sub)| sub.parent.clone()).collect();
+        let parent_identities = self.storage_fetch_many("Identity", "IdentityOf", keys(&parents)).await?;
+        for ((account, sub), parent) in subs.into_iter().zip(parent_identities) {
+            if let Some(parent) = display(parent) {
+                let name = match sub.name {
+                    Some(sub_name) => format!("{}/{}", parent, sub_name),
+                    None => parent,
+                };
+                names.insert(account, name);
+            }
+        }
+
+        Ok(names)
+    }
+}
+
+impl SignedConnection {
+    /// Set the identity of this account, reserving the identity deposit
+    pub async fn set_identity(&self, info: IdentityInfo) -> Result<TxResult> {
+        self.submit(Call::identity_set_identity(&info)?).await
+    }
+
+    /// Clear the identity of this account and its sub-identities
+    pub async fn clear_identity(&self) -> Result<TxResult> {
+        self.submit(Call::identity_clear_identity()).await
+    }
+
+    /// Request a judgement from registrar `reg_index`, paying at most `max_fee`
+    pub async fn request_judgement(&self, reg_index: u32, max_fee: Balance) -> Result<TxResult> {
+        self.submit(Call::identity_request_judgement(reg_index, max_fee)).await
+    }
+
+    /// Replace the sub-identities of this account
+    pub async fn set_subs(&self, subs: Vec<(AccountId32, String)>) -> Result<TxResult> {
+        self.submit(Call::identity_set_subs(&subs)?).await
+    }
+
+    /// Add a sub-identity to this account
+    pub async fn add_sub(&self, sub: AccountId32, name: &str) -> Result<TxResult> {
+        self.submit(Call::identity_add_sub(&sub, name)?).await
+    }
+
+    /// Rename a sub-identity of this account
+    pub async fn rename_sub(&self, sub: AccountId32, name: &str) -> Result<TxResult> {
+        self.submit(Call::identity_rename_sub(&sub, name)?).await
+    }
+
+    /// Remove a sub-identity of this account
+    pub async fn remove_sub(&self, sub: AccountId32) -> Result<TxResult> {
+        self.submit(Call::identity_remove_sub(&sub)).await
+    }
+
+    /// Stop being a sub-identity of the parent account
+    pub async fn quit_sub(&self) -> Result<TxResult> {
+        self.submit(Call::identity_quit_sub()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::substrate::value::test_util::named;
+
+    fn raw(text: &str) -> DecodedValue {
+        let bytes = text.bytes().map(|byte| DecodedValue::U128(byte as u128)).collect();
+        DecodedValue::Variant(
+            format!("Raw{}", text.len()),
+            Box::new(DecodedValue::Unnamed(vec![DecodedValue::Unnamed(bytes)])),
+        )
+    }
+
+    #[test]
+    fn test_identity_from_value() {
+        let none = DecodedValue::Variant("None".to_string(), Box::default());
+        let info = named(vec![
+            ("display", raw("Selendra Foundation")),
+            ("legal", none.clone()),
+            ("web", raw("selendra.org")),
+            ("email", none),
+        ]);
+        let judgements = DecodedValue::Unnamed(vec![DecodedValue::Unnamed(vec![DecodedValue::Unnamed(vec![
+            DecodedValue::U128(0),
+            DecodedValue::Variant("KnownGood".to_string(), Box::default()),
+        ])])]);
+        let registration = named(vec![
+            ("judgements", judgements),
+            ("deposit", DecodedValue::U128(100)),
+            ("info", info),
+        ]);
+        // Newer runtimes pair the registration with an optional username
+        let value = DecodedValue::Unnamed(vec![registration, DecodedValue::Variant("None".to_string(), Box::default())]);
+
+        let identity = Identity::from_value(&value).unwrap();
+        assert_eq!(identity.info.display.as_deref(), Some("Selendra Foundation"));
+        assert_eq!(identity.info.web.as_deref(), Some("selendra.org"));
+        assert_eq!(identity.info.legal, None);
+        assert_eq!(identity.judgements, vec![(0, Judgement::KnownGood)]);
+        assert!(identity.is_verified());
+        println!("✅ Identity decoding test passed");
+    }
+
+    #[test]
+    fn test_data_value_limits() {
+        assert!(data_value(Some("a")).is_ok());
+        assert!(data_value(Some(&"a".repeat(MAX_IDENTITY_FIELD_LEN))).is_ok());
+        assert!(data_value(Some(&"a".repeat(MAX_IDENTITY_FIELD_LEN + 1))).is_err());
+
+        let single = DecodedValue::Variant(
+            "Raw1".to_string(),
+            Box::new(DecodedValue::Unnamed(vec![DecodedValue::Unnamed(vec![DecodedValue::U128(b'x' as u128)])])),
+        );
+        assert_eq!(data_text(&single).as_deref(), Some("x"));
+        assert_eq!(data_text(&raw("")), None);
+        println!("✅ Identity data test passed");
+    }
+}
@@ -3,19 +3,55 @@
 //! Client for interacting with Substrate-based chains.
 
 pub mod client;
+pub mod blocks;
+pub mod batch;
+pub mod calls;
+pub mod fees;
+pub mod identity;
 pub mod types;
 pub mod account;
+pub mod aleph;
 pub mod events;
 pub mod contracts;
+pub mod democracy;
+pub mod multisig;
+pub mod offline;
+pub mod pools;
+pub mod proxy;
+pub mod runtime;
+pub mod staking;
+pub mod storage;
+pub mod treasury;
+pub mod value;
+pub mod vesting;
 
 pub use client::*;
+pub use blocks::*;
+pub use batch::*;
+pub use calls::*;
+pub use identity::*;
 pub use types::*;
 pub use account::*;
+pub use aleph::*;
 pub use events::*;
 pub use contracts::*;
+pub use democracy::*;
+pub use multisig::*;
+pub use offline::*;
+pub use pools::*;
+pub use proxy::*;
+pub use runtime::*;
+pub use staking::*;
+pub use storage::*;
+pub use treasury::*;
+pub use value::*;
+pub use vesting::*;
+
+// Balances of an account, not the keypair record of the account manager
+pub use types::AccountData;
 
 // Task 1.6: REMOVED redundant placeholder files:
-// - storage.rs (all methods returned Ok(None))
+// - storage.rs (all methods returned Ok(None)), since replaced by the dynamic storage API
 // - extrinsics.rs (all methods returned random hashes)
 // - metadata.rs (placeholder implementation)
 // These are now handled by selendra_client wrapper in client.rs
@@ -0,0 +1,271 @@
+//! Multisig accounts backed by `pallet_multisig`
+
+use crate::substrate::calls::Call;
+use crate::substrate::client::{account_value, Connection, SignedConnection};
+use crate::substrate::types::{Balance, TxResult, Weight};
+use crate::substrate::value::DecodedValue;
+use crate::types::{Result, SDKError};
+use scale_codec::Encode;
+use serde::{Deserialize, Serialize};
+use sp_runtime::AccountId32;
+use subxt::dynamic::Value;
+
+/// Position of the extrinsic that opened a multisig operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timepoint {
+    /// Block number
+    pub height: u32,
+    /// Extrinsic index in the block
+    pub index: u32,
+}
+
+impl Timepoint {
+    /// Timepoint of an included extrinsic
+    pub fn new(height: u32, index: u32) -> Self {
+        Self { height, index }
+    }
+}
+
+/// Multisig account defined by its signatories and approval threshold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultisigAccount {
+    signatories: Vec<AccountId32>,
+    threshold: u16,
+}
+
+impl MultisigAccount {
+    /// Create a multisig from its signatories, in any order, and threshold
+    pub fn new(mut signatories: Vec<AccountId32>, threshold: u16) -> Result<Self> {
+        signatories.sort();
+        signatories.dedup();
+
+        if signatories.len() < 2 {
+            return Err(SDKError::Validation("A multisig needs at least two distinct signatories".to_string()));
+        }
+        if threshold < 2 || threshold as usize > signatories.len() {
+            return Err(SDKError::Validation(format!(
+                "Multisig threshold must be between 2 and {}, got {}",
+                signatories.len(),
+                threshold
+            )));
+        }
+
+        Ok(Self { signatories, threshold })
+    }
+
+    /// Sorted signatories of the multisig
+    pub fn signatories(&self) -> &[AccountId32] {
+        &self.signatories
+    }
+
+    /// Number of approvals required to dispatch a call
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    /// Account id of the multisig
+    pub fn account_id(&self) -> AccountId32 {
+        multisig_account_id(&self.signatories, self.threshold)
+    }
+
+    /// Signatories other than `who`, as passed to the multisig calls
+    pub fn other_signatories(&self, who: &AccountId32) -> Result<Vec<AccountId32>> {
+        if !self.signatories.contains(who) {
+            return Err(SDKError::Validation(format!("{} is not a signatory of the multisig", who)));
+        }
+        Ok(self.signatories.iter().filter(|s| *s != who).cloned().collect())
+    }
+}
+
+/// Derive the account id of a multisig, as `pallet_multisig::Pallet::multi_account_id`
+pub fn multisig_account_id(signatories: &[AccountId32], threshold: u16) -> AccountId32 {
+    let mut sorted = signatories.to_vec();
+    sorted.sort();
+
+    let entropy = (b"modlpy/utilisuba", sorted, threshold).using_encoded(sp_core::hashing::blake2_256);
+    AccountId32::from(entropy)
+}
+
+/// Pending multisig operation, from `Multisig::Multisigs`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingMultisig {
+    /// Hash of the call being approved
+    pub call_hash: [u8; 32],
+    /// Timepoint of the first approval, needed by later approvals
+    pub when: Timepoint,
+    /// Deposit reserved from the depositor
+    pub deposit: Balance,
+    /// Account that opened the operation
+    pub depositor: AccountId32,
+    /// Signatories that approved so far
+    pub approvals: Vec<AccountId32>,
+}
+
+impl PendingMultisig {
+    /// Decode a `Multisig` storage value
+    fn from_value(call_hash: [u8; 32], value: &DecodedValue) -> Result<Self> {
+        let invalid = || SDKError::Query("Invalid Multisig storage value".to_string());
+        let when = value.field("when").ok_or_else(invalid)?;
+
+        Ok(Self {
+            call_hash,
+            when: Timepoint {
+                height: when.field("height").and_then(|h| h.as_u128()).ok_or_else(invalid)? as u32,
+                index: when.field("index").and_then(|i| i.as_u128()).ok_or_else(invalid)? as u32,
+            },
+            deposit: value.field("deposit").and_then(|d| d.as_u128()).ok_or_else(invalid)?,
+            depositor: value.field("depositor").and_then(|d| d.as_account_id()).ok_or_else(invalid)?,
+            approvals: value.field("approvals").map(|a| a.as_account_ids()).unwrap_or_default(),
+        })
+    }
+}
+
+impl Connection {
+    /// Pending operations of a multisig account
+    pub async fn get_multisigs(&self, multisig: &AccountId32) -> Result<Vec<PendingMultisig>> {
+        let entries = self.storage_iter("Multisig", "Multisigs", vec![account_value(multisig)]).await?;
+
+        entries
+            .iter()
+            .map(|entry| {
+                let call_hash: [u8; 32] = entry.keys.get(1)
+                    .and_then(|hash| hash.as_bytes())
+                    .and_then(|hash| hash.try_into().ok())
+                    .ok_or_else(|| SDKError::Query("Invalid Multisig storage key".to_string()))?;
+                PendingMultisig::from_value(call_hash, &entry.value)
+            })
+            .collect()
+    }
+
+    /// Pending operation of a multisig account for a call hash
+    pub async fn get_multisig(&self, multisig: &AccountId32, call_hash: [u8; 32]) -> Result<Option<PendingMultisig>> {
+        let keys = vec![account_value(multisig), Value::from_bytes(call_hash)];
+        self.storage_fetch("Multisig", "Multisigs", keys)
+            .await?
+            .map(|value| PendingMultisig::from_value(call_hash, &value))
+            .transpose()
+    }
+}
+
+impl SignedConnection {
+    /// Approve `call` for a multisig, dispatching it if this is the final approval
+    ///
+    /// `timepoint` is `None` for the first approval and the `when` of the pending
+    /// operation otherwise. `max_weight` defaults to the weight of `call`.
+    pub async fn as_multi(
+        &self,
+        multisig: &MultisigAccount,
+        call: Call,
+        timepoint: Option<Timepoint>,
+        max_weight: Option<Weight>,
+    ) -> Result<TxResult> {
+        let other_signatories = multisig.other_signatories(&self.origin())?;
+        let max_weight = match max_weight {
+            Some(weight) => weight,
+            None => self.estimate_fee(&call).await?.weight,
+        };
+
+        self.submit(Call::multisig_as_multi(
+            multisig.threshold(),
+            &other_signatories,
+            timepoint,
+            call,
+            max_weight,
+        ))
+        .await
+    }
+
+    /// Approve a call for a multisig by its hash, without dispatching it
+    ///
+    /// Only the hash is known here, so `max_weight` is the weight of the approved
+    /// call, e.g. the `weight` of its `estimate_fee`.
+    pub async fn approve_as_multi(
+        &self,
+        multisig: &MultisigAccount,
+        call_hash: [u8; 32],
+        timepoint: Option<Timepoint>,
+        max_weight: Weight,
+    ) -> Result<TxResult> {
+        let other_signatories = multisig.other_signatories(&self.origin())?;
+        self.submit(Call::multisig_approve_as_multi(
+            multisig.threshold(),
+            &other_signatories,
+            timepoint,
+            call_hash,
+            max_weight,
+        ))
+        .await
+    }
+
+    /// Cancel a pending multisig operation opened by this account
+    pub async fn cancel_as_multi(
+        &self,
+        multisig: &MultisigAccount,
+        timepoint: Timepoint,
+        call_hash: [u8; 32],
+    ) -> Result<TxResult> {
+        let other_signatories = multisig.other_signatories(&self.origin())?;
+        self.submit(Call::multisig_cancel_as_multi(
+            multisig.threshold(),
+            &other_signatories,
+            timepoint,
+            call_hash,
+        ))
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::substrate::client::keypair_from_string;
+
+    #[test]
+    fn test_multisig_account_id_order_independent() {
+        let alice = keypair_from_string("//Alice").account_id();
+        let bob = keypair_from_string("//Bob").account_id();
+        let charlie = keypair_from_string("//Charlie").account_id();
+
+        let a = multisig_account_id(&[alice.clone(), bob.clone(), charlie.clone()], 2);
+        let b = multisig_account_id(&[charlie.clone(), alice.clone(), bob.clone()], 2);
+        let c = multisig_account_id(&[alice.clone(), bob.clone(), charlie.clone()], 3);
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        println!("✅ Multisig account id: {}", a);
+    }
+
+    #[test]
+    fn test_multisig_account_id_known_address() {
+        use sp_core::crypto::Ss58Codec;
+
+        let alice = keypair_from_string("//Alice").account_id();
+        let bob = keypair_from_string("//Bob").account_id();
+        let charlie = keypair_from_string("//Charlie").account_id();
+
+        // 2-of-3 multisig of the dev accounts, as derived by polkadot.js
+        let multisig = MultisigAccount::new(vec![alice, bob, charlie], 2).unwrap();
+        assert_eq!(
+            multisig.account_id().to_ss58check(),
+            "5DjYJStmdZ2rcqXbXGX7TW85JsrW6uG4y9MUcLq2BoPMpRA7"
+        );
+        println!("✅ Known multisig address test passed");
+    }
+
+    #[test]
+    fn test_multisig_validation() {
+        let alice = keypair_from_string("//Alice").account_id();
+        let bob = keypair_from_string("//Bob").account_id();
+        let charlie = keypair_from_string("//Charlie").account_id();
+
+        assert!(MultisigAccount::new(vec![alice.clone(), alice.clone()], 2).is_err());
+        assert!(MultisigAccount::new(vec![alice.clone(), bob.clone()], 3).is_err());
+
+        let multisig = MultisigAccount::new(vec![charlie.clone(), alice.clone(), bob.clone()], 2).unwrap();
+        let others = multisig.other_signatories(&bob).unwrap();
+        assert_eq!(others.len(), 2);
+        assert!(!others.contains(&bob));
+        assert!(others.windows(2).all(|pair| pair[0] < pair[1]));
+        println!("✅ Multisig validation test passed");
+    }
+}
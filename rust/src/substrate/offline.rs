@@ -0,0 +1,433 @@
+//! Offline signing of Substrate extrinsics
+//!
+//! Signing is split in three steps that can each run on a different machine:
+//! an online host prepares a [`SigningPayload`] with
+//! [`Connection::create_signing_payload`], an offline host signs it with
+//! [`SigningPayload::sign`], and the online host submits the resulting
+//! [`SignedPayload`] with [`Connection::submit_signed`].
+
+use crate::substrate::calls::Call;
+use crate::substrate::client::{from_block_hash, Connection, KeyPair, TxStatus};
+use crate::substrate::types::{Balance, TxResult};
+use crate::types::{Result, SDKError};
+use scale_codec::{Compact, Decode, Encode};
+use serde::{Deserialize, Serialize};
+use sp_core::{sr25519, Pair, H256};
+use sp_runtime::generic::Era;
+use sp_runtime::AccountId32;
+use subxt::tx::SubmittableExtrinsic;
+
+/// Signed extensions whose extra and additional data the payload can encode
+const SUPPORTED_EXTENSIONS: &[&str] = &[
+    "CheckNonZeroSender",
+    "CheckSpecVersion",
+    "CheckTxVersion",
+    "CheckGenesis",
+    "CheckMortality",
+    "CheckEra",
+    "CheckNonce",
+    "CheckWeight",
+    "ChargeTransactionPayment",
+];
+
+/// Version byte of a signed v4 extrinsic
+const SIGNED_EXTRINSIC_V4: u8 = 0x80 | 4;
+
+/// Default number of blocks an offline signed extrinsic stays valid for
+pub const DEFAULT_MORTALITY: u64 = 64;
+
+/// Serde helpers encoding byte vectors as `0x` prefixed hex strings
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        hex::decode(s.trim_start_matches("0x")).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Validity period of a signed extrinsic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub enum SigningEra {
+    /// Valid forever
+    Immortal,
+    /// Valid for `period` blocks starting at the block with the given `phase`
+    Mortal {
+        /// Number of blocks the extrinsic is valid for, a power of two
+        period: u64,
+        /// Position of the birth block within the period
+        phase: u64,
+    },
+}
+
+impl SigningEra {
+    /// Runtime representation of the era
+    fn era(&self) -> Era {
+        match *self {
+            SigningEra::Immortal => Era::Immortal,
+            SigningEra::Mortal { period, phase } => Era::Mortal(period, phase),
+        }
+    }
+}
+
+/// Options for preparing a signing payload
+#[derive(Debug, Clone)]
+pub struct SigningOptions {
+    /// Nonce to sign with, the next account nonce if `None`
+    pub nonce: Option<u32>,
+    /// Tip for the block author
+    pub tip: Balance,
+    /// Number of blocks the extrinsic stays valid for, immortal if `None`
+    pub mortality: Option<u64>,
+}
+
+impl Default for SigningOptions {
+    fn default() -> Self {
+        Self {
+            nonce: None,
+            tip: 0,
+            mortality: Some(DEFAULT_MORTALITY),
+        }
+    }
+}
+
+/// Check every signed extension is one the payload can encode
+fn check_extensions<'a>(extensions: impl IntoIterator<Item = &'a str>) -> Result<()> {
+    match extensions.into_iter().find(|extension| !SUPPORTED_EXTENSIONS.contains(extension)) {
+        Some(extension) => Err(SDKError::NotImplemented(format!(
+            "Offline signing does not support the {} signed extension",
+            extension
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Everything an offline host needs to sign an extrinsic
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub struct SigningPayload {
+    /// Account expected to sign the payload
+    pub signer: AccountId32,
+    /// SCALE encoded call
+    #[serde(with = "hex_bytes")]
+    pub call_data: Vec<u8>,
+    /// Account nonce
+    pub nonce: u32,
+    /// Validity period
+    pub era: SigningEra,
+    /// Tip for the block author
+    pub tip: Balance,
+    /// Runtime spec version
+    pub spec_version: u32,
+    /// Runtime transaction version
+    pub transaction_version: u32,
+    /// Genesis block hash
+    pub genesis_hash: H256,
+    /// Hash of the block the era starts at, the genesis hash for immortal extrinsics
+    pub checkpoint_hash: H256,
+    /// Signed extensions of the runtime, in metadata order
+    pub extensions: Vec<String>,
+}
+
+impl SigningPayload {
+    /// Signed extension data included in the extrinsic, in extension order
+    fn extra(&self) -> Vec<u8> {
+        let mut extra = Vec::new();
+        for extension in &self.extensions {
+            match extension.as_str() {
+                "CheckMortality" | "CheckEra" => self.era.era().encode_to(&mut extra),
+                "CheckNonce" => Compact(self.nonce).encode_to(&mut extra),
+                "ChargeTransactionPayment" => Compact(self.tip).encode_to(&mut extra),
+                _ => {}
+            }
+        }
+        extra
+    }
+
+    /// Signed extension data that is signed but not included in the extrinsic, in extension order
+    fn additional(&self) -> Vec<u8> {
+        let mut additional = Vec::new();
+        for extension in &self.extensions {
+            match extension.as_str() {
+                "CheckSpecVersion" => self.spec_version.encode_to(&mut additional),
+                "CheckTxVersion" => self.transaction_version.encode_to(&mut additional),
+                "CheckGenesis" => self.genesis_hash.encode_to(&mut additional),
+                "CheckMortality" | "CheckEra" => self.checkpoint_hash.encode_to(&mut additional),
+                _ => {}
+            }
+        }
+        additional
+    }
+
+    /// Bytes the signer signs: hashed with Blake2-256 when longer than 256 bytes
+    pub fn signable_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.call_data.clone();
+        bytes.extend(self.extra());
+        bytes.extend(self.additional());
+
+        if bytes.len() > 256 {
+            sp_core::hashing::blake2_256(&bytes).to_vec()
+        } else {
+            bytes
+        }
+    }
+
+    /// Sign the payload, checking the key pair matches the expected signer
+    pub fn sign(&self, keypair: &KeyPair) -> Result<SignedPayload> {
+        check_extensions(self.extensions.iter().map(String::as_str))?;
+        if keypair.account_id() != self.signer {
+            return Err(SDKError::InvalidKey(format!(
+                "Payload must be signed by {}, got {}",
+                self.signer,
+                keypair.account_id()
+            )));
+        }
+
+        let signature = keypair.pair.sign(&self.signable_bytes());
+        Ok(SignedPayload {
+            payload: self.clone(),
+            signature: signature.0.to_vec(),
+        })
+    }
+
+    /// Serialize the payload to JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Deserialize a payload from JSON
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serialize the payload to SCALE bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.encode()
+    }
+
+    /// Deserialize a payload from SCALE bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::decode(&mut &bytes[..])
+            .map_err(|e| SDKError::SerializationError(format!("Invalid signing payload: {}", e)))
+    }
+}
+
+/// Signing payload with the sr25519 signature of its signer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub struct SignedPayload {
+    /// The signed payload
+    pub payload: SigningPayload,
+    /// sr25519 signature of the signable bytes
+    #[serde(with = "hex_bytes")]
+    pub signature: Vec<u8>,
+}
+
+impl SignedPayload {
+    /// Whether the signature is valid for the payload and its signer
+    pub fn verify(&self) -> bool {
+        let Ok(signature) = <[u8; 64]>::try_from(self.signature.as_slice()) else {
+            return false;
+        };
+        let signer: &[u8; 32] = self.payload.signer.as_ref();
+
+        sr25519::Pair::verify(
+            &sr25519::Signature::from_raw(signature),
+            self.payload.signable_bytes(),
+            &sr25519::Public::from_raw(*signer),
+        )
+    }
+
+    /// Length prefixed signed v4 extrinsic, ready for `author_submitExtrinsic`
+    pub fn extrinsic(&self) -> Result<Vec<u8>> {
+        if !self.verify() {
+            return Err(SDKError::SigningError("Signature does not match the payload".to_string()));
+        }
+        check_extensions(self.payload.extensions.iter().map(String::as_str))?;
+
+        let signer: &[u8; 32] = self.payload.signer.as_ref();
+        let mut extrinsic = vec![SIGNED_EXTRINSIC_V4];
+        // MultiAddress::Id
+        extrinsic.push(0x00);
+        extrinsic.extend_from_slice(signer);
+        // MultiSignature::Sr25519
+        extrinsic.push(0x01);
+        extrinsic.extend_from_slice(&self.signature);
+        extrinsic.extend(self.payload.extra());
+        extrinsic.extend_from_slice(&self.payload.call_data);
+
+        Ok(extrinsic.encode())
+    }
+
+    /// Hash of the extrinsic, as reported by the node once submitted
+    pub fn tx_hash(&self) -> Result<H256> {
+        Ok(H256(sp_core::hashing::blake2_256(&self.extrinsic()?)))
+    }
+
+    /// Serialize the signed payload to JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Deserialize a signed payload from JSON
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serialize the signed payload to SCALE bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.encode()
+    }
+
+    /// Deserialize a signed payload from SCALE bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::decode(&mut &bytes[..])
+            .map_err(|e| SDKError::SerializationError(format!("Invalid signed payload: {}", e)))
+    }
+}
+
+impl Connection {
+    /// Prepare the payload an offline host signs for `call` sent by `signer`
+    pub async fn create_signing_payload(
+        &self,
+        signer: &AccountId32,
+        call: &Call,
+        options: SigningOptions,
+    ) -> Result<SigningPayload> {
+        let client = self.client();
+        let metadata = client.metadata();
+
+        let extensions: Vec<String> = metadata.extrinsic()
+            .signed_extensions()
+            .iter()
+            .map(|extension| extension.identifier().to_string())
+            .collect();
+        check_extensions(extensions.iter().map(String::as_str))?;
+
+        let nonce = match options.nonce {
+            Some(nonce) => nonce,
+            None => self.get_next_nonce(signer).await?,
+        };
+
+        let genesis_hash = from_block_hash(&client.genesis_hash());
+        let (era, checkpoint_hash) = match options.mortality {
+            None => (SigningEra::Immortal, genesis_hash),
+            Some(period) => {
+                let best = self.get_latest_block().await?;
+                let era = Era::mortal(period, best.number);
+                let checkpoint = self.get_block_hash(era.birth(best.number))
+                    .await?
+                    .ok_or_else(|| SDKError::NotFound("Era birth block not found".to_string()))?;
+                match era {
+                    Era::Mortal(period, phase) => (SigningEra::Mortal { period, phase }, checkpoint),
+                    Era::Immortal => (SigningEra::Immortal, genesis_hash),
+                }
+            }
+        };
+
+        let runtime_version = client.runtime_version();
+        Ok(SigningPayload {
+            signer: signer.clone(),
+            call_data: call.encode(&metadata)?,
+            nonce,
+            era,
+            tip: options.tip,
+            spec_version: runtime_version.spec_version,
+            transaction_version: runtime_version.transaction_version,
+            genesis_hash,
+            checkpoint_hash,
+            extensions,
+        })
+    }
+
+    /// Submit an extrinsic signed offline, waiting for the given inclusion level
+    pub async fn submit_signed(&self, signed: &SignedPayload, status: TxStatus) -> Result<TxResult> {
+        let extrinsic = SubmittableExtrinsic::from_bytes(self.client().clone(), signed.extrinsic()?);
+        let progress = extrinsic.submit_and_watch()
+            .await
+            .map_err(|e| SDKError::Transaction(format!("Failed to submit signed extrinsic: {}", e)))?;
+        self.watch(progress, status).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::substrate::client::keypair_from_string;
+
+    fn payload(call_data: Vec<u8>) -> SigningPayload {
+        SigningPayload {
+            signer: keypair_from_string("//Alice").account_id(),
+            call_data,
+            nonce: 5,
+            era: SigningEra::Mortal { period: 64, phase: 10 },
+            tip: 0,
+            spec_version: 1,
+            transaction_version: 1,
+            genesis_hash: H256::repeat_byte(1),
+            checkpoint_hash: H256::repeat_byte(2),
+            extensions: SUPPORTED_EXTENSIONS.iter()
+                .filter(|extension| **extension != "CheckEra")
+                .map(|extension| extension.to_string())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_signable_bytes_hashed_when_long() {
+        let short = payload(vec![0u8; 16]);
+        assert!(short.signable_bytes().starts_with(&short.call_data));
+
+        let long = payload(vec![0u8; 300]);
+        assert_eq!(long.signable_bytes().len(), 32);
+        println!("✅ Signable bytes test passed");
+    }
+
+    #[test]
+    fn test_extrinsic_layout() {
+        let keypair = keypair_from_string("//Alice");
+        let signed = payload(vec![4, 0]).sign(&keypair).unwrap();
+        let extrinsic = signed.extrinsic().unwrap();
+
+        let mut input = &extrinsic[..];
+        let body = Vec::<u8>::decode(&mut input).unwrap();
+        assert!(input.is_empty());
+        assert_eq!(body[0], SIGNED_EXTRINSIC_V4);
+        assert_eq!(&body[2..34], keypair.account_id().as_ref() as &[u8]);
+        assert_eq!(body[34], 0x01);
+        assert!(body.ends_with(&[4, 0]));
+        println!("✅ Extrinsic layout test passed");
+    }
+
+    #[test]
+    fn test_sign_with_wrong_key() {
+        let bob = keypair_from_string("//Bob");
+        assert!(payload(vec![4, 0]).sign(&bob).is_err());
+
+        let mut signed = payload(vec![4, 0]).sign(&keypair_from_string("//Alice")).unwrap();
+        signed.payload.nonce += 1;
+        assert!(!signed.verify());
+        assert!(signed.extrinsic().is_err());
+        println!("✅ Signer mismatch test passed");
+    }
+
+    #[test]
+    fn test_extensions_in_metadata_order() {
+        let mut signing = payload(vec![4, 0]);
+        signing.tip = 3;
+        let era = signing.era.era();
+
+        assert_eq!(signing.extra(), (era, Compact(5u32), Compact(3u128)).encode());
+        assert_eq!(signing.additional(), (1u32, 1u32, H256::repeat_byte(1), H256::repeat_byte(2)).encode());
+
+        signing.extensions = vec!["ChargeTransactionPayment".to_string(), "CheckNonce".to_string(), "CheckGenesis".to_string()];
+        assert_eq!(signing.extra(), (Compact(3u128), Compact(5u32)).encode());
+        assert_eq!(signing.additional(), H256::repeat_byte(1).encode());
+
+        signing.extensions.push("ChargeAssetTxPayment".to_string());
+        assert!(signing.sign(&keypair_from_string("//Alice")).is_err());
+        println!("✅ Signed extension order test passed");
+    }
+}
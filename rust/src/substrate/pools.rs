@@ -0,0 +1,371 @@
+//! Nomination pools backed by `pallet_nomination_pools`
+
+use crate::substrate::calls::Call;
+use crate::substrate::client::{account_value, pallet_account, Connection, SignedConnection};
+use crate::substrate::events::EventDecoder;
+use crate::substrate::types::{Balance, TxResult};
+use crate::substrate::value::{bounded_items, DecodedValue};
+use crate::types::{Result, SDKError};
+use scale_codec::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use sp_runtime::AccountId32;
+use subxt::dynamic::Value;
+
+/// Pallet id used when the runtime does not expose `NominationPools::PalletId`
+const DEFAULT_POOLS_PALLET_ID: [u8; 8] = *b"py/nopls";
+
+/// State of a pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PoolState {
+    /// Anyone can join
+    Open,
+    /// No new members can join
+    Blocked,
+    /// The pool is winding down, members can only leave
+    Destroying,
+}
+
+impl PoolState {
+    /// Variant name of the state in the runtime
+    pub fn name(&self) -> &'static str {
+        match self {
+            PoolState::Open => "Open",
+            PoolState::Blocked => "Blocked",
+            PoolState::Destroying => "Destroying",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Open" => Some(PoolState::Open),
+            "Blocked" => Some(PoolState::Blocked),
+            "Destroying" => Some(PoolState::Destroying),
+            _ => None,
+        }
+    }
+
+    /// Dynamic value of the state, for building runtime calls
+    pub(crate) fn to_value(self) -> Value {
+        Value::unnamed_variant(self.name(), [])
+    }
+}
+
+/// Source of the funds bonded by `bond_extra`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BondExtra {
+    /// Bond more of the member's free balance
+    FreeBalance(Balance),
+    /// Bond the member's pending rewards
+    Rewards,
+}
+
+impl BondExtra {
+    /// Dynamic value of the bond source, for building runtime calls
+    pub(crate) fn to_value(self) -> Value {
+        match self {
+            BondExtra::FreeBalance(amount) => Value::unnamed_variant("FreeBalance", [Value::u128(amount)]),
+            BondExtra::Rewards => Value::unnamed_variant("Rewards", []),
+        }
+    }
+}
+
+/// Accounts administering a pool
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolRoles {
+    /// Account that created the pool and holds its deposit
+    pub depositor: AccountId32,
+    /// Account that can change every role
+    pub root: Option<AccountId32>,
+    /// Account that chooses the validators of the pool
+    pub nominator: Option<AccountId32>,
+    /// Account that can change the state of the pool
+    pub bouncer: Option<AccountId32>,
+}
+
+/// Bonded pool, from `NominationPools::BondedPools`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BondedPool {
+    /// Pool id
+    pub id: u32,
+    /// Points issued to members
+    pub points: Balance,
+    /// Number of members
+    pub member_counter: u32,
+    /// Current state
+    pub state: PoolState,
+    /// Administrative accounts
+    pub roles: PoolRoles,
+    /// Current commission in parts per billion, if the runtime supports pool commission
+    pub commission: Option<u32>,
+}
+
+impl BondedPool {
+    fn from_value(id: u32, value: &DecodedValue) -> Option<Self> {
+        let roles = value.field("roles")?;
+        let role = |name: &str| roles.field(name).and_then(|role| role.as_option()).and_then(|role| role.as_account_id());
+
+        Some(Self {
+            id,
+            points: value.field("points")?.as_u128()?,
+            member_counter: value.field("member_counter")?.as_u128()? as u32,
+            state: PoolState::from_name(value.field("state")?.variant_name()?)?,
+            roles: PoolRoles {
+                depositor: roles.field("depositor")?.as_account_id()?,
+                root: role("root"),
+                nominator: role("nominator"),
+                // Older runtimes call the bouncer `state_toggler`
+                bouncer: role("bouncer").or_else(|| role("state_toggler")),
+            },
+            commission: value.field("commission")
+                .and_then(|commission| commission.field("current"))
+                .and_then(|current| current.as_option())
+                .and_then(|current| current.at(0))
+                .and_then(|commission| commission.as_u128())
+                .map(|commission| commission as u32),
+        })
+    }
+}
+
+/// Reward bookkeeping of a pool, from `NominationPools::RewardPools`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardPool {
+    /// Reward counter at the last recorded payout, as a `FixedU128` inner value
+    pub last_recorded_reward_counter: u128,
+    /// Total payouts received by the pool at the last record
+    pub last_recorded_total_payouts: Balance,
+    /// Rewards claimed by members so far
+    pub total_rewards_claimed: Balance,
+    /// Commission not yet claimed by the pool
+    pub total_commission_pending: Balance,
+    /// Commission claimed so far
+    pub total_commission_claimed: Balance,
+}
+
+impl RewardPool {
+    fn from_value(value: &DecodedValue) -> Option<Self> {
+        let balance = |name: &str| value.field(name).and_then(|field| field.as_u128()).unwrap_or(0);
+        Some(Self {
+            last_recorded_reward_counter: value.field("last_recorded_reward_counter")?.as_u128()?,
+            last_recorded_total_payouts: balance("last_recorded_total_payouts"),
+            total_rewards_claimed: balance("total_rewards_claimed"),
+            total_commission_pending: balance("total_commission_pending"),
+            total_commission_claimed: balance("total_commission_claimed"),
+        })
+    }
+}
+
+/// Membership of an account in a pool, from `NominationPools::PoolMembers`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolMember {
+    /// Pool joined
+    pub pool_id: u32,
+    /// Points held in the pool
+    pub points: Balance,
+    /// Reward counter at the last claim, as a `FixedU128` inner value
+    pub last_recorded_reward_counter: u128,
+    /// Points being unbonded, by era at which they can be withdrawn
+    pub unbonding_eras: Vec<(u32, Balance)>,
+}
+
+impl PoolMember {
+    fn from_value(value: &DecodedValue) -> Option<Self> {
+        Some(Self {
+            pool_id: value.field("pool_id")?.as_u128()? as u32,
+            points: value.field("points")?.as_u128()?,
+            last_recorded_reward_counter: value.field("last_recorded_reward_counter")?.as_u128()?,
+            unbonding_eras: bounded_items(value.field("unbonding_eras")?)
+                .into_iter()
+                .filter_map(|entry| Some((entry.at(0)?.as_u128()? as u32, entry.at(1)?.as_u128()?)))
+                .collect(),
+        })
+    }
+
+    /// Points that can be withdrawn at `era`
+    pub fn withdrawable(&self, era: u32) -> Balance {
+        self.unbonding_eras.iter().filter(|(unlock_era, _)| *unlock_era <= era).map(|(_, points)| points).sum()
+    }
+}
+
+/// Outcome of a `NominationPools::create` extrinsic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedPool {
+    /// Result of the extrinsic
+    pub tx: TxResult,
+    /// Id of the new pool, from `NominationPools::Created`
+    pub pool_id: Option<u32>,
+}
+
+impl Connection {
+    /// A bonded pool, `None` if it does not exist
+    pub async fn get_pool(&self, pool_id: u32) -> Result<Option<BondedPool>> {
+        match self.storage_fetch("NominationPools", "BondedPools", vec![Value::u128(pool_id as u128)]).await? {
+            Some(pool) => BondedPool::from_value(pool_id, &pool)
+                .map(Some)
+                .ok_or_else(|| SDKError::Query("Invalid BondedPools storage value".to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Every bonded pool, ordered by id
+    pub async fn get_pools(&self) -> Result<Vec<BondedPool>> {
+        let mut pools: Vec<_> = self.storage_iter("NominationPools", "BondedPools", vec![])
+            .await?
+            .iter()
+            .filter_map(|entry| {
+                let id = entry.keys.first()?.as_u128()? as u32;
+                BondedPool::from_value(id, &entry.value)
+            })
+            .collect();
+        pools.sort_by_key(|pool| pool.id);
+        Ok(pools)
+    }
+
+    /// Reward bookkeeping of a pool, `None` if it does not exist
+    pub async fn get_reward_pool(&self, pool_id: u32) -> Result<Option<RewardPool>> {
+        match self.storage_fetch("NominationPools", "RewardPools", vec![Value::u128(pool_id as u128)]).await? {
+            Some(pool) => RewardPool::from_value(&pool)
+                .map(Some)
+                .ok_or_else(|| SDKError::Query("Invalid RewardPools storage value".to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Pool membership of an account, `None` if it is not in a pool
+    pub async fn get_pool_member(&self, account: &AccountId32) -> Result<Option<PoolMember>> {
+        match self.storage_fetch("NominationPools", "PoolMembers", vec![account_value(account)]).await? {
+            Some(member) => PoolMember::from_value(&member)
+                .map(Some)
+                .ok_or_else(|| SDKError::Query("Invalid PoolMembers storage value".to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Metadata of a pool, usually its display name
+    pub async fn get_pool_metadata(&self, pool_id: u32) -> Result<String> {
+        let metadata = self.storage_fetch_or_default("NominationPools", "Metadata", vec![Value::u128(pool_id as u128)]).await?;
+        Ok(String::from_utf8_lossy(&metadata.as_bytes().unwrap_or_default()).into_owned())
+    }
+
+    /// Rewards an account can claim from its pool, via `NominationPoolsApi_pending_rewards`
+    pub async fn get_pending_rewards(&self, account: &AccountId32) -> Result<Balance> {
+        let bytes = self.client().rpc()
+            .state_call_raw("NominationPoolsApi_pending_rewards", Some(&account.encode()), self.at_hash())
+            .await
+            .map_err(|e| SDKError::Query(format!("Failed to query pending rewards: {}", e)))?;
+        Balance::decode(&mut &bytes[..])
+            .map_err(|e| SDKError::SerializationError(format!("Failed to decode pending rewards: {}", e)))
+    }
+
+    /// Stash account holding the bonded funds of a pool
+    pub fn pool_bonded_account(&self, pool_id: u32) -> AccountId32 {
+        // `AccountType::Bonded` is the first variant
+        pallet_account(self.pallet_id("NominationPools", DEFAULT_POOLS_PALLET_ID), (0u8, pool_id))
+    }
+}
+
+impl SignedConnection {
+    /// Join a pool, bonding `amount`
+    pub async fn pool_join(&self, amount: Balance, pool_id: u32) -> Result<TxResult> {
+        self.submit(Call::pool_join(amount, pool_id)).await
+    }
+
+    /// Bond more funds or the pending rewards into the pool of this account
+    pub async fn pool_bond_extra(&self, extra: BondExtra) -> Result<TxResult> {
+        self.submit(Call::pool_bond_extra(extra)).await
+    }
+
+    /// Pay out the pending pool rewards of this account
+    pub async fn pool_claim_payout(&self) -> Result<TxResult> {
+        self.submit(Call::pool_claim_payout()).await
+    }
+
+    /// Start unbonding `points` of this account's pool membership
+    pub async fn pool_unbond(&self, points: Balance) -> Result<TxResult> {
+        self.submit(Call::pool_unbond(&self.origin(), points)).await
+    }
+
+    /// Withdraw the unlocked pool funds of this account
+    ///
+    /// The slashing span count of the pool's stash is looked up from the membership.
+    pub async fn pool_withdraw_unbonded(&self) -> Result<TxResult> {
+        let member = self.origin();
+        let connection = self.connection();
+        let pool_id = connection.get_pool_member(&member)
+            .await?
+            .ok_or_else(|| SDKError::NotFound(format!("{} is not a pool member", member)))?
+            .pool_id;
+        let spans = connection.get_slashing_spans(&connection.pool_bonded_account(pool_id)).await?;
+        self.submit(Call::pool_withdraw_unbonded(&member, spans)).await
+    }
+
+    /// Create a pool bonding `amount`, with this account as depositor
+    pub async fn pool_create(
+        &self,
+        amount: Balance,
+        root: AccountId32,
+        nominator: AccountId32,
+        bouncer: AccountId32,
+    ) -> Result<CreatedPool> {
+        let tx = self.submit(Call::pool_create(amount, &root, &nominator, &bouncer)).await?;
+
+        let decoder = EventDecoder::from_connection(&self.connection());
+        let pool_id = match tx.find_events("NominationPools", "Created").next() {
+            Some(raw) => decoder.decode(raw)?.field("pool_id").and_then(|id| id.as_u128()).map(|id| id as u32),
+            None => None,
+        };
+
+        Ok(CreatedPool { tx, pool_id })
+    }
+
+    /// Set the validators of a pool, as its root or nominator
+    pub async fn pool_nominate(&self, pool_id: u32, validators: Vec<AccountId32>) -> Result<TxResult> {
+        self.submit(Call::pool_nominate(pool_id, &validators)).await
+    }
+
+    /// Change the state of a pool, as its root or bouncer
+    pub async fn pool_set_state(&self, pool_id: u32, state: PoolState) -> Result<TxResult> {
+        self.submit(Call::pool_set_state(pool_id, state)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::substrate::value::test_util::{account, named, some};
+
+    #[test]
+    fn test_bonded_pool_from_value() {
+        let none = DecodedValue::Variant("None".to_string(), Box::default());
+        let value = named(vec![
+            ("member_counter", DecodedValue::U128(3)),
+            ("points", DecodedValue::U128(5_000)),
+            ("roles", named(vec![
+                ("depositor", account(1)),
+                ("root", some(account(1))),
+                ("nominator", some(account(2))),
+                ("state_toggler", none),
+            ])),
+            ("state", DecodedValue::Variant("Blocked".to_string(), Box::default())),
+        ]);
+
+        let pool = BondedPool::from_value(7, &value).unwrap();
+        assert_eq!(pool.id, 7);
+        assert_eq!(pool.state, PoolState::Blocked);
+        assert_eq!(pool.roles.nominator, Some(AccountId32::from([2u8; 32])));
+        assert_eq!(pool.roles.bouncer, None);
+        assert_eq!(pool.commission, None);
+        println!("✅ Bonded pool decoding test passed");
+    }
+
+    #[test]
+    fn test_pool_account() {
+        let account = pallet_account(DEFAULT_POOLS_PALLET_ID, (0u8, 1u32));
+        let bytes: &[u8] = account.as_ref();
+
+        assert_eq!(&bytes[..4], b"modl");
+        assert_eq!(&bytes[4..12], b"py/nopls");
+        assert_eq!(&bytes[12..17], &[0, 1, 0, 0, 0]);
+        assert!(bytes[17..].iter().all(|byte| *byte == 0));
+        println!("✅ Pool account derivation test passed");
+    }
+}
@@ -0,0 +1,162 @@
+//! Runtime version, chain properties and runtime upgrade tracking
+
+use crate::substrate::client::{BlockHash, Connection};
+use crate::substrate::types::{ChainInfo, MetadataVersion};
+use crate::types::{Result, SDKError};
+use futures::{stream, Stream};
+use scale_codec::Decode;
+use std::collections::HashMap;
+use std::pin::Pin;
+
+/// Stream of runtime versions, one per applied runtime upgrade
+pub type RuntimeUpgradeStream = Pin<Box<dyn Stream<Item = Result<MetadataVersion>> + Send>>;
+
+/// Metadata version assumed for runtimes without the `Metadata_metadata_versions` API
+const DEFAULT_METADATA_VERSION: u32 = 14;
+
+impl ChainInfo {
+    /// Native token symbol, from the `tokenSymbol` property
+    pub fn token_symbol(&self) -> Option<&str> {
+        self.properties.get("tokenSymbol").and_then(|symbol| symbol.split(',').next())
+    }
+
+    /// Native token decimals, from the `tokenDecimals` property
+    pub fn token_decimals(&self) -> Option<u8> {
+        self.properties.get("tokenDecimals")
+            .and_then(|decimals| decimals.split(',').next())
+            .and_then(|decimals| decimals.parse().ok())
+    }
+
+    /// SS58 address prefix, from the `ss58Format` property
+    pub fn ss58_prefix(&self) -> Option<u16> {
+        self.properties.get("ss58Format").and_then(|prefix| prefix.parse().ok())
+    }
+}
+
+/// Render a chain property as a string, joining multi-token lists with commas
+fn property_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(items) => items.iter().map(property_string).collect::<Vec<_>>().join(","),
+        other => other.to_string(),
+    }
+}
+
+impl Connection {
+    /// Highest metadata version the runtime at `at` can provide
+    async fn metadata_version(&self, at: Option<BlockHash>) -> u32 {
+        self.client().rpc()
+            .state_call_raw("Metadata_metadata_versions", None, at)
+            .await
+            .ok()
+            .and_then(|bytes| Vec::<u32>::decode(&mut &bytes[..]).ok())
+            .and_then(|versions| versions.into_iter().filter(|v| *v != u32::MAX).max())
+            .unwrap_or(DEFAULT_METADATA_VERSION)
+    }
+
+    /// Runtime version, from `state_getRuntimeVersion`
+    pub async fn runtime_version(&self) -> Result<MetadataVersion> {
+        let version = self.client().rpc().runtime_version(self.at_hash())
+            .await
+            .map_err(|e| SDKError::Query(format!("Failed to fetch runtime version: {}", e)))?;
+
+        Ok(MetadataVersion {
+            spec_version: version.spec_version,
+            transaction_version: version.transaction_version,
+            metadata_version: self.metadata_version(self.at_hash()).await,
+        })
+    }
+
+    /// Chain name, runtime spec version and chain properties
+    pub async fn chain_info(&self) -> Result<ChainInfo> {
+        let rpc = self.client().rpc();
+        let chain_name = rpc.system_chain()
+            .await
+            .map_err(|e| SDKError::Query(format!("Failed to fetch chain name: {}", e)))?;
+        let properties = rpc.system_properties()
+            .await
+            .map_err(|e| SDKError::Query(format!("Failed to fetch chain properties: {}", e)))?;
+
+        Ok(ChainInfo {
+            chain_name,
+            version: self.runtime_version().await?.spec_version,
+            properties: properties
+                .iter()
+                .map(|(key, value)| (key.clone(), property_string(value)))
+                .collect::<HashMap<_, _>>(),
+        })
+    }
+
+    /// Subscribe to runtime upgrades
+    ///
+    /// Each time the node reports a new `spec_version`, the metadata of this
+    /// connection and of every connection cloned from the same node client is
+    /// refreshed before the new version is yielded, so calls and storage queries
+    /// keep encoding correctly. The stream must be polled for updates to apply;
+    /// `EventDecoder`s built before an upgrade keep the old metadata.
+    pub async fn subscribe_runtime_upgrades(&self) -> Result<RuntimeUpgradeStream> {
+        let updater = self.client().updater();
+        let updates = updater.runtime_updates()
+            .await
+            .map_err(|e| SDKError::Connection(format!("Failed to subscribe to runtime updates: {}", e)))?;
+        let connection = self.clone();
+
+        Ok(Box::pin(stream::unfold(
+            (updater, updates, connection),
+            |(updater, mut updates, connection)| async move {
+                loop {
+                    let update = match updates.next().await? {
+                        Ok(update) => update,
+                        Err(e) => {
+                            let error = SDKError::Query(format!("Runtime update subscription failed: {}", e));
+                            return Some((Err(error), (updater, updates, connection)));
+                        }
+                    };
+
+                    let spec_version = update.runtime_version().spec_version;
+                    let transaction_version = update.runtime_version().transaction_version;
+                    // The subscription also reports the current version, which is already applied
+                    if updater.apply_update(update).is_err() {
+                        continue;
+                    }
+
+                    let version = MetadataVersion {
+                        spec_version,
+                        transaction_version,
+                        metadata_version: connection.metadata_version(None).await,
+                    };
+                    return Some((Ok(version), (updater, updates, connection)));
+                }
+            },
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_info_properties() {
+        let properties = serde_json::json!({
+            "tokenSymbol": ["SEL", "KSEL"],
+            "tokenDecimals": 18,
+            "ss58Format": 204,
+        });
+
+        let info = ChainInfo {
+            chain_name: "Selendra".to_string(),
+            version: 1,
+            properties: properties.as_object()
+                .unwrap()
+                .iter()
+                .map(|(key, value)| (key.clone(), property_string(value)))
+                .collect(),
+        };
+
+        assert_eq!(info.token_symbol(), Some("SEL"));
+        assert_eq!(info.token_decimals(), Some(18));
+        assert_eq!(info.ss58_prefix(), Some(204));
+        println!("✅ Chain properties test passed");
+    }
+}
@@ -0,0 +1,730 @@
+//! Staking lifecycle, exposures and reward analytics
+//!
+//! Besides the unbonding, payee and payout calls, this module reads the era
+//! records of `pallet_staking` (exposures, reward points, validator payouts and
+//! preferences) and estimates what a nominator earns from them.
+
+use crate::substrate::calls::Call;
+use crate::substrate::client::{account_value, Connection, SignedConnection};
+use crate::substrate::storage::StorageEntry;
+use crate::substrate::types::{Balance, TxResult};
+use crate::substrate::value::{bounded_items, DecodedValue};
+use crate::types::{Result, SDKError};
+use serde::{Deserialize, Serialize};
+use sp_runtime::{AccountId32, Perbill};
+use std::collections::{HashMap, HashSet};
+use subxt::dynamic::Value;
+
+/// Where staking rewards are paid to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RewardDestination {
+    /// Paid to the stash and added to the bond
+    Staked,
+    /// Paid to the stash, not bonded
+    Stash,
+    /// Paid to the controller account
+    Controller,
+    /// Paid to another account
+    Account(AccountId32),
+    /// Not paid out
+    None,
+}
+
+impl RewardDestination {
+    /// Dynamic value of the destination, for building runtime calls
+    pub(crate) fn to_value(&self) -> Value {
+        match self {
+            RewardDestination::Staked => Value::unnamed_variant("Staked", []),
+            RewardDestination::Stash => Value::unnamed_variant("Stash", []),
+            RewardDestination::Controller => Value::unnamed_variant("Controller", []),
+            RewardDestination::Account(account) => Value::unnamed_variant("Account", [account_value(account)]),
+            RewardDestination::None => Value::unnamed_variant("None", []),
+        }
+    }
+
+    /// Decode a `RewardDestination` storage value
+    pub(crate) fn from_value(value: &DecodedValue) -> Option<Self> {
+        Some(match value.variant_name()? {
+            "Staked" => RewardDestination::Staked,
+            "Stash" => RewardDestination::Stash,
+            "Controller" => RewardDestination::Controller,
+            "Account" => RewardDestination::Account(value.at(0)?.as_account_id()?),
+            "None" => RewardDestination::None,
+            _ => return None,
+        })
+    }
+}
+
+/// Stake of a nominator behind a validator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndividualExposure {
+    /// Nominator stash
+    pub who: AccountId32,
+    /// Amount exposed
+    pub value: Balance,
+}
+
+impl IndividualExposure {
+    fn from_value(value: &DecodedValue) -> Option<Self> {
+        Some(Self {
+            who: value.field("who")?.as_account_id()?,
+            value: value.field("value")?.as_u128()?,
+        })
+    }
+}
+
+/// Stake backing a validator in an era, from `Staking::ErasStakers`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Exposure {
+    /// Total stake, own and nominated
+    pub total: Balance,
+    /// Stake of the validator itself
+    pub own: Balance,
+    /// Stakes of the nominators
+    pub others: Vec<IndividualExposure>,
+}
+
+impl Exposure {
+    /// Decode an `Exposure`, or the `PagedExposureMetadata` overview without nominators
+    fn from_value(value: &DecodedValue) -> Option<Self> {
+        Some(Self {
+            total: value.field("total")?.as_u128()?,
+            own: value.field("own")?.as_u128()?,
+            others: value.field("others")
+                .map(|others| others.items().into_iter().filter_map(IndividualExposure::from_value).collect())
+                .unwrap_or_default(),
+        })
+    }
+
+    /// Add the nominators of an `ExposurePage`
+    fn extend_page(&mut self, page: &DecodedValue) {
+        if let Some(others) = page.field("others") {
+            self.others.extend(others.items().into_iter().filter_map(IndividualExposure::from_value));
+        }
+    }
+
+    /// Stake of `nominator` behind the validator, 0 if it does not back it
+    pub fn stake_of(&self, nominator: &AccountId32) -> Balance {
+        self.others.iter().filter(|other| &other.who == nominator).map(|other| other.value).sum()
+    }
+}
+
+/// Reward points earned by validators in an era, from `Staking::ErasRewardPoints`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EraRewardPoints {
+    /// Points of all validators
+    pub total: u32,
+    /// Points of each validator
+    pub individual: Vec<(AccountId32, u32)>,
+}
+
+impl EraRewardPoints {
+    fn from_value(value: &DecodedValue) -> Option<Self> {
+        Some(Self {
+            total: value.field("total")?.as_u128()? as u32,
+            individual: value.field("individual")?
+                .items()
+                .into_iter()
+                .filter_map(|entry| Some((entry.at(0)?.as_account_id()?, entry.at(1)?.as_u128()? as u32)))
+                .collect(),
+        })
+    }
+
+    /// Points earned by `validator`
+    pub fn points_of(&self, validator: &AccountId32) -> u32 {
+        self.individual.iter().find(|(account, _)| account == validator).map_or(0, |(_, points)| *points)
+    }
+
+    /// Share of the era payout earned by `validator`
+    pub fn payout_of(&self, validator: &AccountId32, era_payout: Balance) -> Balance {
+        if self.total == 0 {
+            return 0;
+        }
+        Perbill::from_rational(self.points_of(validator), self.total).mul_floor(era_payout)
+    }
+}
+
+/// Validator preferences, from `Staking::Validators` or `Staking::ErasValidatorPrefs`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorPrefs {
+    /// Commission in parts per billion
+    pub commission: u32,
+    /// Whether the validator accepts no new nominations
+    pub blocked: bool,
+}
+
+impl ValidatorPrefs {
+    fn from_value(value: &DecodedValue) -> Option<Self> {
+        Some(Self {
+            commission: value.field("commission")?.as_u128()? as u32,
+            blocked: value.field("blocked")?.as_bool()?,
+        })
+    }
+
+    /// Commission in percent
+    pub fn commission_percent(&self) -> f64 {
+        self.commission as f64 / 10_000_000.0
+    }
+
+    /// Part of a validator payout left to stakers once commission is taken
+    fn after_commission(&self, payout: Balance) -> Balance {
+        payout - Perbill::from_parts(self.commission).mul_floor(payout)
+    }
+}
+
+/// Funds scheduled to unlock at an era
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnlockChunk {
+    /// Amount unlocking
+    pub value: Balance,
+    /// Era at which the funds can be withdrawn
+    pub era: u32,
+}
+
+/// Bonding state of a stash, from `Staking::Ledger`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakingLedger {
+    /// Stash account
+    pub stash: AccountId32,
+    /// Total bonded, including unlocking funds
+    pub total: Balance,
+    /// Bonded funds still at stake
+    pub active: Balance,
+    /// Funds being unbonded
+    pub unlocking: Vec<UnlockChunk>,
+    /// Eras whose rewards were claimed before paged payouts
+    pub claimed_rewards: Vec<u32>,
+}
+
+impl StakingLedger {
+    fn from_value(value: &DecodedValue) -> Option<Self> {
+        let claimed = value.field("claimed_rewards").or_else(|| value.field("legacy_claimed_rewards"));
+        Some(Self {
+            stash: value.field("stash")?.as_account_id()?,
+            total: value.field("total")?.as_u128()?,
+            active: value.field("active")?.as_u128()?,
+            unlocking: bounded_items(value.field("unlocking")?)
+                .into_iter()
+                .filter_map(|chunk| {
+                    Some(UnlockChunk {
+                        value: chunk.field("value")?.as_u128()?,
+                        era: chunk.field("era")?.as_u128()? as u32,
+                    })
+                })
+                .collect(),
+            claimed_rewards: claimed.map(era_indices).unwrap_or_default(),
+        })
+    }
+
+    /// Funds that can be withdrawn at `era`
+    pub fn withdrawable(&self, era: u32) -> Balance {
+        self.unlocking.iter().filter(|chunk| chunk.era <= era).map(|chunk| chunk.value).sum()
+    }
+}
+
+/// Estimated reward of a nominator for an era
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NominatorRewardEstimate {
+    /// Era of the estimate
+    pub era: u32,
+    /// Stake of the nominator across its validators
+    pub stake: Balance,
+    /// Total reward
+    pub reward: Balance,
+    /// Reward from each backed validator
+    pub by_validator: Vec<(AccountId32, Balance)>,
+}
+
+/// Return a validator paid its nominators in an era
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidatorReturn {
+    /// Validator stash
+    pub validator: AccountId32,
+    /// Preferences of the validator in the era
+    pub prefs: ValidatorPrefs,
+    /// Total stake behind the validator
+    pub total_stake: Balance,
+    /// Reward points earned
+    pub points: u32,
+    /// Payout of the validator, before commission
+    pub payout: Balance,
+    /// Reward per unit of nominated stake, e.g. `0.0004` for 0.04% in the era
+    pub nominator_return: f64,
+}
+
+/// Number of eras payouts can be claimed for, if the runtime does not expose it
+const DEFAULT_HISTORY_DEPTH: u32 = 84;
+
+impl Connection {
+    /// Index of the active era, `None` before the first era starts
+    pub async fn get_active_era(&self) -> Result<Option<u32>> {
+        Ok(self.storage_fetch("Staking", "ActiveEra", vec![])
+            .await?
+            .and_then(|era| era.field("index").and_then(|index| index.as_u128()))
+            .map(|index| index as u32))
+    }
+
+    /// Where the rewards of a stash are paid to
+    pub async fn get_payee(&self, stash: &AccountId32) -> Result<Option<RewardDestination>> {
+        Ok(self.storage_fetch("Staking", "Payee", vec![account_value(stash)])
+            .await?
+            .and_then(|payee| RewardDestination::from_value(&payee)))
+    }
+
+    /// Number of slashing spans of a stash, as required by `withdraw_unbonded`
+    pub async fn get_slashing_spans(&self, stash: &AccountId32) -> Result<u32> {
+        let spans = self.storage_fetch("Staking", "SlashingSpans", vec![account_value(stash)]).await?;
+        Ok(spans
+            .map(|spans| spans.field("prior").map_or(0, |prior| prior.items().len()) as u32 + 1)
+            .unwrap_or(0))
+    }
+
+    /// Preferences a validator currently advertises, `None` if it is not validating
+    pub async fn get_validator_prefs(&self, validator: &AccountId32) -> Result<Option<ValidatorPrefs>> {
+        Ok(self.storage_fetch("Staking", "Validators", vec![account_value(validator)])
+            .await?
+            .and_then(|prefs| ValidatorPrefs::from_value(&prefs)))
+    }
+
+    /// Preferences of a validator as snapshotted for an era
+    pub async fn get_era_validator_prefs(&self, era: u32, validator: &AccountId32) -> Result<ValidatorPrefs> {
+        Ok(self.era_validator_prefs(era, std::slice::from_ref(validator)).await?.remove(0))
+    }
+
+    /// Era preferences of several validators in one query, defaulting where not set
+    async fn era_validator_prefs(&self, era: u32, validators: &[AccountId32]) -> Result<Vec<ValidatorPrefs>> {
+        let keys = validators
+            .iter()
+            .map(|validator| vec![Value::u128(era as u128), account_value(validator)])
+            .collect();
+        Ok(self.storage_fetch_many("Staking", "ErasValidatorPrefs", keys)
+            .await?
+            .into_iter()
+            .map(|prefs| prefs.and_then(|prefs| ValidatorPrefs::from_value(&prefs)).unwrap_or_default())
+            .collect())
+    }
+
+    /// Total payout to validators for an era, `None` until the era has ended
+    pub async fn get_era_validator_reward(&self, era: u32) -> Result<Option<Balance>> {
+        Ok(self.storage_fetch("Staking", "ErasValidatorReward", vec![Value::u128(era as u128)])
+            .await?
+            .and_then(|reward| reward.as_u128()))
+    }
+
+    /// Reward points earned by validators in an era
+    pub async fn get_era_reward_points(&self, era: u32) -> Result<EraRewardPoints> {
+        let points = self.storage_fetch_or_default("Staking", "ErasRewardPoints", vec![Value::u128(era as u128)]).await?;
+        EraRewardPoints::from_value(&points)
+            .ok_or_else(|| SDKError::Query("Invalid ErasRewardPoints storage value".to_string()))
+    }
+
+    /// Ledger of a stash, `None` if it is not bonded
+    pub async fn get_ledger(&self, stash: &AccountId32) -> Result<Option<StakingLedger>> {
+        let controller = self.storage_fetch("Staking", "Bonded", vec![account_value(stash)])
+            .await?
+            .and_then(|controller| controller.as_account_id())
+            .unwrap_or_else(|| stash.clone());
+
+        match self.storage_fetch("Staking", "Ledger", vec![account_value(&controller)]).await? {
+            Some(ledger) => StakingLedger::from_value(&ledger)
+                .map(Some)
+                .ok_or_else(|| SDKError::Query("Invalid Ledger storage value".to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Stake behind a validator in an era, `None` if it was not elected
+    ///
+    /// On runtimes with paged exposures the nominators of every page are collected.
+    pub async fn get_exposure(&self, era: u32, validator: &AccountId32) -> Result<Option<Exposure>> {
+        let keys = || vec![Value::u128(era as u128), account_value(validator)];
+        let invalid = || SDKError::Query("Invalid exposure storage value".to_string());
+
+        if self.has_storage("Staking", "ErasStakersOverview") {
+            if let Some(overview) = self.storage_fetch("Staking", "ErasStakersOverview", keys()).await? {
+                let mut exposure = Exposure::from_value(&overview).ok_or_else(invalid)?;
+                let page_count = overview.field("page_count").and_then(|count| count.as_u128()).unwrap_or(0);
+                let page_keys = (0..page_count)
+                    .map(|page| {
+                        let mut keys = keys();
+                        keys.push(Value::u128(page));
+                        keys
+                    })
+                    .collect();
+                for page in self.storage_fetch_many("Staking", "ErasStakersPaged", page_keys).await?.iter().flatten() {
+                    exposure.extend_page(page);
+                }
+                return Ok(Some(exposure));
+            }
+        }
+
+        match self.storage_fetch("Staking", "ErasStakers", keys()).await? {
+            Some(exposure) => {
+                let exposure = Exposure::from_value(&exposure).ok_or_else(invalid)?;
+                Ok((exposure.total > 0).then_some(exposure))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Exposures of every validator elected in an era
+    pub async fn get_era_exposures(&self, era: u32) -> Result<Vec<(AccountId32, Exposure)>> {
+        let keys = || vec![Value::u128(era as u128)];
+        let validator_of = |entry: &StorageEntry| entry.keys.get(1).and_then(|key| key.as_account_id());
+
+        let exposures: Vec<_> = self.storage_iter("Staking", "ErasStakers", keys())
+            .await?
+            .iter()
+            .filter_map(|entry| Some((validator_of(entry)?, Exposure::from_value(&entry.value)?)))
+            .collect();
+        if !exposures.is_empty() || !self.has_storage("Staking", "ErasStakersOverview") {
+            return Ok(exposures);
+        }
+
+        let mut paged: HashMap<AccountId32, Exposure> = self.storage_iter("Staking", "ErasStakersOverview", keys())
+            .await?
+            .iter()
+            .filter_map(|entry| Some((validator_of(entry)?, Exposure::from_value(&entry.value)?)))
+            .collect();
+        for page in self.storage_iter("Staking", "ErasStakersPaged", keys()).await? {
+            if let Some(exposure) = validator_of(&page).and_then(|validator| paged.get_mut(&validator)) {
+                exposure.extend_page(&page.value);
+            }
+        }
+        Ok(paged.into_iter().collect())
+    }
+
+    /// Estimate the reward of a nominator for an ended era
+    ///
+    /// The payout of each backed validator is split by reward points, its
+    /// commission is taken, and the rest is shared pro rata to stake, as
+    /// `payout_stakers` does.
+    pub async fn estimate_nominator_reward(&self, nominator: &AccountId32, era: u32) -> Result<NominatorRewardEstimate> {
+        let era_payout = self.get_era_validator_reward(era)
+            .await?
+            .ok_or_else(|| SDKError::NotFound(format!("No validator payout recorded for era {}", era)))?;
+        let points = self.get_era_reward_points(era).await?;
+        let backed: Vec<_> = self.get_era_exposures(era)
+            .await?
+            .into_iter()
+            .filter(|(_, exposure)| exposure.stake_of(nominator) > 0)
+            .collect();
+        let validators: Vec<_> = backed.iter().map(|(validator, _)| validator.clone()).collect();
+        let prefs = self.era_validator_prefs(era, &validators).await?;
+
+        let mut estimate = NominatorRewardEstimate { era, ..Default::default() };
+        for ((validator, exposure), prefs) in backed.into_iter().zip(prefs) {
+            let stake = exposure.stake_of(nominator);
+            let payout = prefs.after_commission(points.payout_of(&validator, era_payout));
+            let reward = Perbill::from_rational(stake, exposure.total).mul_floor(payout);
+
+            estimate.stake += stake;
+            estimate.reward += reward;
+            estimate.by_validator.push((validator, reward));
+        }
+        Ok(estimate)
+    }
+
+    /// Return every validator paid its nominators in an ended era, best first
+    pub async fn get_validator_returns(&self, era: u32) -> Result<Vec<ValidatorReturn>> {
+        let era_payout = self.get_era_validator_reward(era)
+            .await?
+            .ok_or_else(|| SDKError::NotFound(format!("No validator payout recorded for era {}", era)))?;
+        let points = self.get_era_reward_points(era).await?;
+        let exposures = self.get_era_exposures(era).await?;
+        let validators: Vec<_> = exposures.iter().map(|(validator, _)| validator.clone()).collect();
+        let prefs = self.era_validator_prefs(era, &validators).await?;
+
+        let mut returns: Vec<_> = exposures
+            .into_iter()
+            .zip(prefs)
+            .map(|((validator, exposure), prefs)| {
+                let payout = points.payout_of(&validator, era_payout);
+                let nominator_return = match exposure.total {
+                    0 => 0.0,
+                    total => prefs.after_commission(payout) as f64 / total as f64,
+                };
+                ValidatorReturn {
+                    points: points.points_of(&validator),
+                    validator,
+                    prefs,
+                    total_stake: exposure.total,
+                    payout,
+                    nominator_return,
+                }
+            })
+            .collect();
+        returns.sort_by(|a, b| b.nominator_return.total_cmp(&a.nominator_return));
+        Ok(returns)
+    }
+
+    /// Eras within the history depth whose rewards for `validator` have not been paid out
+    ///
+    /// Only eras in which the validator was elected are considered. An era is
+    /// returned while any of its exposure pages is unclaimed, oldest first; see
+    /// `get_unclaimed_pages` for the pages of an era.
+    pub async fn get_unclaimed_eras(&self, validator: &AccountId32) -> Result<Vec<u32>> {
+        let active_era = match self.get_active_era().await? {
+            Some(era) => era,
+            None => return Ok(Vec::new()),
+        };
+        let history_depth = self.integer_constant("Staking", "HistoryDepth")
+            .await
+            .map(|depth| depth as u32)
+            .unwrap_or(DEFAULT_HISTORY_DEPTH);
+        let eras: Vec<u32> = (active_era.saturating_sub(history_depth)..active_era).collect();
+        let era_keys = || eras.iter().map(|era| vec![Value::u128(*era as u128), account_value(validator)]).collect();
+
+        // Exposures are paged on newer runtimes, with the page count in the overview
+        let paged = self.has_storage("Staking", "ErasStakersOverview");
+        let exposures = if paged {
+            self.storage_fetch_many("Staking", "ErasStakersOverview", era_keys()).await?
+        } else {
+            self.storage_fetch_many("Staking", "ErasStakers", era_keys()).await?
+        };
+
+        // Eras claimed before paged rewards were introduced are listed in the ledger
+        let legacy_claimed: HashSet<u32> = self.get_ledger(validator)
+            .await?
+            .map(|ledger| ledger.claimed_rewards.into_iter().collect())
+            .unwrap_or_default();
+
+        let claimed_pages = if self.has_storage("Staking", "ClaimedRewards") {
+            self.storage_fetch_many("Staking", "ClaimedRewards", era_keys()).await?
+        } else {
+            vec![None; eras.len()]
+        };
+
+        Ok(eras
+            .iter()
+            .zip(exposures)
+            .zip(claimed_pages)
+            .filter_map(|((era, exposure), claimed)| {
+                let exposure = exposure?;
+                if exposure.field("total").and_then(|total| total.as_u128()).unwrap_or(0) == 0 {
+                    return None;
+                }
+                if legacy_claimed.contains(era) {
+                    return None;
+                }
+
+                let claimed = claimed.map(|pages| era_indices(&pages)).unwrap_or_default();
+                (!unclaimed_pages(page_count(&exposure, paged), &claimed).is_empty()).then_some(*era)
+            })
+            .collect())
+    }
+
+    /// Exposure pages of `validator` in `era` whose rewards have not been paid out
+    ///
+    /// Runtimes without paged exposures have a single page 0. Pages are returned
+    /// in order, ready for `stake_payout_stakers_by_page`.
+    pub async fn get_unclaimed_pages(&self, validator: &AccountId32, era: u32) -> Result<Vec<u32>> {
+        let keys = || vec![Value::u128(era as u128), account_value(validator)];
+
+        let paged = self.has_storage("Staking", "ErasStakersOverview");
+        let exposure = if paged {
+            self.storage_fetch("Staking", "ErasStakersOverview", keys()).await?
+        } else {
+            self.storage_fetch("Staking", "ErasStakers", keys()).await?
+        };
+        let Some(exposure) = exposure else {
+            return Ok(Vec::new());
+        };
+        if exposure.field("total").and_then(|total| total.as_u128()).unwrap_or(0) == 0 {
+            return Ok(Vec::new());
+        }
+
+        let legacy_claimed = self.get_ledger(validator)
+            .await?
+            .is_some_and(|ledger| ledger.claimed_rewards.contains(&era));
+        if legacy_claimed {
+            return Ok(Vec::new());
+        }
+
+        let claimed = if self.has_storage("Staking", "ClaimedRewards") {
+            self.storage_fetch("Staking", "ClaimedRewards", keys())
+                .await?
+                .map(|pages| era_indices(&pages))
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        Ok(unclaimed_pages(page_count(&exposure, paged), &claimed))
+    }
+}
+
+/// Number of exposure pages of an `ErasStakersOverview` or legacy `ErasStakers` value
+fn page_count(exposure: &DecodedValue, paged: bool) -> u32 {
+    if paged {
+        exposure.field("page_count").and_then(|count| count.as_u128()).unwrap_or(1) as u32
+    } else {
+        1
+    }
+}
+
+/// Pages below `page_count` that are not in `claimed`
+fn unclaimed_pages(page_count: u32, claimed: &[u32]) -> Vec<u32> {
+    (0..page_count).filter(|page| !claimed.contains(page)).collect()
+}
+
+/// Era or page indices of a `BoundedVec<u32>` or `Vec<u32>` value
+fn era_indices(value: &DecodedValue) -> Vec<u32> {
+    bounded_items(value).into_iter().filter_map(|item| item.as_u128()).map(|era| era as u32).collect()
+}
+
+impl SignedConnection {
+    /// Schedule bonded funds to be unlocked after the bonding duration
+    pub async fn stake_unbond(&self, amount: u128) -> Result<TxResult> {
+        self.submit(Call::stake_unbond(amount)).await
+    }
+
+    /// Unlock funds whose bonding duration has passed
+    ///
+    /// The slashing span count the runtime requires is looked up for the stash.
+    pub async fn stake_withdraw_unbonded(&self) -> Result<TxResult> {
+        let spans = self.connection().get_slashing_spans(&self.origin()).await?;
+        self.submit(Call::stake_withdraw_unbonded(spans)).await
+    }
+
+    /// Bond again funds that are still unlocking
+    pub async fn stake_rebond(&self, amount: u128) -> Result<TxResult> {
+        self.submit(Call::stake_rebond(amount)).await
+    }
+
+    /// Set where staking rewards are paid to
+    pub async fn stake_set_payee(&self, payee: RewardDestination) -> Result<TxResult> {
+        self.submit(Call::stake_set_payee(payee)).await
+    }
+
+    /// Make the stash its own controller
+    pub async fn stake_set_controller(&self) -> Result<TxResult> {
+        self.submit(Call::stake_set_controller()).await
+    }
+
+    /// Pay out the rewards of a validator and its nominators for an era
+    ///
+    /// With paged exposures each call only pays the next unclaimed page; use
+    /// `stake_payout_all_pages` to pay every page of the era.
+    pub async fn stake_payout_stakers(&self, validator: AccountId32, era: u32) -> Result<TxResult> {
+        self.submit(Call::stake_payout_stakers(&validator, era)).await
+    }
+
+    /// Pay out the rewards of one exposure page of a validator for an era
+    pub async fn stake_payout_stakers_by_page(&self, validator: AccountId32, era: u32, page: u32) -> Result<TxResult> {
+        self.submit(Call::stake_payout_stakers_by_page(&validator, era, page)).await
+    }
+
+    /// Pay out every unclaimed exposure page of a validator for an era, one transaction per page
+    ///
+    /// Stops at the first page that fails to submit. Runtimes without paged
+    /// exposures are paid with a single `payout_stakers` call.
+    pub async fn stake_payout_all_pages(&self, validator: AccountId32, era: u32) -> Result<Vec<TxResult>> {
+        let pages = self.connection().get_unclaimed_pages(&validator, era).await?;
+        let paged = self.connection().has_storage("Staking", "ErasStakersOverview");
+
+        let mut results = Vec::with_capacity(pages.len());
+        for page in pages {
+            let call = if paged {
+                Call::stake_payout_stakers_by_page(&validator, era, page)
+            } else {
+                Call::stake_payout_stakers(&validator, era)
+            };
+            results.push(self.submit(call).await?);
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::substrate::value::test_util::{account, named};
+
+    #[test]
+    fn test_reward_destination_from_value() {
+        let payee = DecodedValue::Variant("Account".to_string(), Box::new(DecodedValue::Unnamed(vec![account(3)])));
+        assert_eq!(
+            RewardDestination::from_value(&payee),
+            Some(RewardDestination::Account(AccountId32::from([3u8; 32])))
+        );
+
+        let staked = DecodedValue::Variant("Staked".to_string(), Box::default());
+        assert_eq!(RewardDestination::from_value(&staked), Some(RewardDestination::Staked));
+        println!("✅ Reward destination decoding test passed");
+    }
+
+    #[test]
+    fn test_claimed_eras() {
+        let eras = DecodedValue::Unnamed(vec![DecodedValue::U128(10), DecodedValue::U128(11)]);
+        let bounded = DecodedValue::Unnamed(vec![eras.clone()]);
+        let single = DecodedValue::Unnamed(vec![DecodedValue::Unnamed(vec![DecodedValue::U128(7)])]);
+
+        assert_eq!(era_indices(&eras), vec![10, 11]);
+        assert_eq!(era_indices(&bounded), vec![10, 11]);
+        assert_eq!(era_indices(&single), vec![7]);
+        println!("✅ Claimed eras decoding test passed");
+    }
+
+    #[test]
+    fn test_unclaimed_pages() {
+        let overview = named(vec![("total", DecodedValue::U128(1_000)), ("page_count", DecodedValue::U128(3))]);
+        let legacy = named(vec![("total", DecodedValue::U128(1_000))]);
+
+        assert_eq!(page_count(&overview, true), 3);
+        assert_eq!(page_count(&legacy, false), 1);
+        assert_eq!(unclaimed_pages(3, &[]), vec![0, 1, 2]);
+        assert_eq!(unclaimed_pages(3, &[1]), vec![0, 2]);
+        assert!(unclaimed_pages(1, &[0]).is_empty());
+        println!("✅ Unclaimed pages test passed");
+    }
+
+    #[test]
+    fn test_nominator_reward_split() {
+        let exposure = Exposure::from_value(&named(vec![
+            ("total", DecodedValue::U128(1_000)),
+            ("own", DecodedValue::U128(600)),
+            ("others", DecodedValue::Unnamed(vec![named(vec![
+                ("who", account(2)),
+                ("value", DecodedValue::U128(400)),
+            ])])),
+        ])).unwrap();
+        let points = EraRewardPoints::from_value(&named(vec![
+            ("total", DecodedValue::U128(100)),
+            ("individual", DecodedValue::Unnamed(vec![
+                DecodedValue::Unnamed(vec![account(1), DecodedValue::U128(25)]),
+            ])),
+        ])).unwrap();
+        let prefs = ValidatorPrefs { commission: 100_000_000, blocked: false };
+        let validator = AccountId32::from([1u8; 32]);
+        let nominator = AccountId32::from([2u8; 32]);
+
+        assert_eq!(exposure.stake_of(&nominator), 400);
+        assert_eq!(prefs.commission_percent(), 10.0);
+
+        // A quarter of the era payout, less 10% commission, 40% of it to the nominator
+        let payout = points.payout_of(&validator, 1_000_000);
+        assert_eq!(payout, 250_000);
+        assert_eq!(prefs.after_commission(payout), 225_000);
+        assert_eq!(Perbill::from_rational(400u128, exposure.total).mul_floor(prefs.after_commission(payout)), 90_000);
+        println!("✅ Nominator reward split test passed");
+    }
+
+    #[test]
+    fn test_ledger_from_value() {
+        let chunk = |value: u128, era: u128| named(vec![("value", DecodedValue::U128(value)), ("era", DecodedValue::U128(era))]);
+        let ledger = StakingLedger::from_value(&named(vec![
+            ("stash", account(5)),
+            ("total", DecodedValue::U128(300)),
+            ("active", DecodedValue::U128(100)),
+            ("unlocking", DecodedValue::Unnamed(vec![DecodedValue::Unnamed(vec![chunk(50, 10), chunk(150, 12)])])),
+            ("legacy_claimed_rewards", DecodedValue::Unnamed(vec![DecodedValue::Unnamed(vec![DecodedValue::U128(9)])])),
+        ])).unwrap();
+
+        assert_eq!(ledger.stash, AccountId32::from([5u8; 32]));
+        assert_eq!(ledger.unlocking.len(), 2);
+        assert_eq!(ledger.withdrawable(11), 50);
+        assert_eq!(ledger.withdrawable(12), 200);
+        assert_eq!(ledger.claimed_rewards, vec![9]);
+        println!("✅ Staking ledger decoding test passed");
+    }
+}
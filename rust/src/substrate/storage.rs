@@ -0,0 +1,406 @@
+//! Dynamic storage access decoded against runtime metadata
+//!
+//! Any `pallet::entry` can be read by name, with map keys given as dynamic
+//! values or JSON. Storage maps are iterated page by page with
+//! `state_getKeysPaged`, and values of a page are fetched in a single
+//! `state_queryStorageAt` call.
+
+use crate::substrate::client::{BlockHash, Connection};
+use crate::substrate::value::{value_from_json, DecodedValue};
+use crate::types::{Result, SDKError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use subxt::dynamic::Value;
+use subxt::metadata::types::{StorageEntryType, StorageHasher};
+
+/// Default number of keys requested per `state_getKeysPaged` call
+pub const DEFAULT_PAGE_SIZE: u32 = 100;
+
+/// Length of the pallet and entry prefix of a storage key
+const STORAGE_PREFIX_LEN: usize = 32;
+
+/// Entry of a storage map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageEntry {
+    /// Full storage key
+    pub key: Vec<u8>,
+    /// Map keys decoded from the storage key, in order
+    ///
+    /// Only keys hashed with a concatenating hasher (`Blake2_128Concat`,
+    /// `Twox64Concat` or `Identity`) can be recovered; decoding stops at the first
+    /// opaque hasher.
+    pub keys: Vec<DecodedValue>,
+    /// Decoded value
+    pub value: DecodedValue,
+}
+
+/// Page of storage map entries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoragePage {
+    /// Entries of the page
+    pub entries: Vec<StorageEntry>,
+    /// Key to start the next page after, `None` on the last page
+    pub next_key: Option<Vec<u8>>,
+}
+
+/// Hashers and types of a storage entry, from the runtime metadata
+struct EntryLayout {
+    hashers: Vec<StorageHasher>,
+    key_types: Vec<u32>,
+    value_ty: u32,
+}
+
+impl EntryLayout {
+    fn new(metadata: &subxt::Metadata, pallet: &str, entry: &str) -> Result<Self> {
+        let entry_type = metadata.pallet_by_name(pallet)
+            .and_then(|pallet| pallet.storage())
+            .and_then(|storage| storage.entry_by_name(entry))
+            .map(|entry| entry.entry_type().clone())
+            .ok_or_else(|| SDKError::Query(format!("Storage {}::{} not found", pallet, entry)))?;
+
+        match entry_type {
+            StorageEntryType::Plain(value_ty) => Ok(Self {
+                hashers: Vec::new(),
+                key_types: Vec::new(),
+                value_ty,
+            }),
+            StorageEntryType::Map { hashers, key_ty, value_ty } => {
+                // Maps with several hashers take a tuple of keys
+                let key_types = match metadata.types().resolve(key_ty).map(|ty| &ty.type_def) {
+                    Some(scale_info::TypeDef::Tuple(tuple)) if hashers.len() > 1 => {
+                        tuple.fields.iter().map(|field| field.id).collect()
+                    }
+                    _ => vec![key_ty],
+                };
+                Ok(Self { hashers, key_types, value_ty })
+            }
+        }
+    }
+
+    /// Decode the map keys of a full storage key
+    fn decode_keys(&self, key: &[u8], metadata: &subxt::Metadata) -> Vec<DecodedValue> {
+        let mut keys = Vec::new();
+        let mut cursor = key.get(STORAGE_PREFIX_LEN..).unwrap_or_default();
+
+        for (hasher, key_ty) in self.hashers.iter().zip(&self.key_types) {
+            let hash_len = match hasher {
+                StorageHasher::Blake2_128Concat => 16,
+                StorageHasher::Twox64Concat => 8,
+                StorageHasher::Identity => 0,
+                _ => break,
+            };
+            if cursor.len() < hash_len {
+                break;
+            }
+            cursor = &cursor[hash_len..];
+
+            match DecodedValue::decode(&mut cursor, *key_ty, metadata) {
+                Ok(value) => keys.push(value),
+                Err(_) => break,
+            }
+        }
+
+        keys
+    }
+}
+
+/// Key to start the next page after, `None` when a short page ended the map
+fn next_key(page: &[Vec<u8>], page_size: u32) -> Option<Vec<u8>> {
+    if page.len() < page_size as usize {
+        None
+    } else {
+        page.last().cloned()
+    }
+}
+
+impl Connection {
+    /// Block storage queries read from: the block of an `at` view, or the best block
+    async fn storage_block(&self) -> Result<BlockHash> {
+        if let Some(hash) = self.at_hash() {
+            return Ok(hash);
+        }
+
+        self.client().rpc().block_hash(None)
+            .await
+            .map_err(|e| SDKError::Query(format!("Failed to fetch best block hash: {}", e)))?
+            .ok_or_else(|| SDKError::NotFound("Best block hash not found".to_string()))
+    }
+
+    /// Whether the runtime has the given storage entry
+    pub(crate) fn has_storage(&self, pallet: &str, entry: &str) -> bool {
+        self.client().metadata()
+            .pallet_by_name(pallet)
+            .and_then(|pallet| pallet.storage())
+            .and_then(|storage| storage.entry_by_name(entry))
+            .is_some()
+    }
+
+    /// Fetch a storage value, `None` if it is not set
+    ///
+    /// `keys` are the map keys of the entry, empty for plain storage values.
+    pub async fn storage_fetch(&self, pallet: &str, entry: &str, keys: Vec<Value>) -> Result<Option<DecodedValue>> {
+        let address = subxt::dynamic::storage(pallet, entry, keys);
+        let value = self.client().storage().at(self.storage_block().await?)
+            .fetch(&address)
+            .await
+            .map_err(|e| SDKError::Query(format!("Failed to fetch {}::{}: {}", pallet, entry, e)))?;
+
+        value
+            .map(|value| {
+                value.to_value()
+                    .map(DecodedValue::from)
+                    .map_err(|e| SDKError::Query(format!("Failed to decode {}::{}: {}", pallet, entry, e)))
+            })
+            .transpose()
+    }
+
+    /// Fetch a storage value, falling back to its default from the metadata
+    pub async fn storage_fetch_or_default(&self, pallet: &str, entry: &str, keys: Vec<Value>) -> Result<DecodedValue> {
+        let address = subxt::dynamic::storage(pallet, entry, keys);
+        let failed = |e: subxt::Error| SDKError::Query(format!("Failed to fetch {}::{}: {}", pallet, entry, e));
+        self.client().storage().at(self.storage_block().await?)
+            .fetch_or_default(&address)
+            .await
+            .map_err(failed)?
+            .to_value()
+            .map(DecodedValue::from)
+            .map_err(failed)
+    }
+
+    /// Fetch a storage value with map keys given as JSON
+    ///
+    /// See [`value_from_json`] for how JSON keys are interpreted.
+    pub async fn storage_fetch_json(
+        &self,
+        pallet: &str,
+        entry: &str,
+        keys: &[serde_json::Value],
+    ) -> Result<Option<DecodedValue>> {
+        let keys = keys.iter().map(value_from_json).collect::<Result<Vec<_>>>()?;
+        self.storage_fetch(pallet, entry, keys).await
+    }
+
+    /// Fetch the values of several map keys in a single `state_queryStorageAt` call
+    ///
+    /// Values are returned in the order of `keys`, `None` where not set.
+    pub async fn storage_fetch_many(
+        &self,
+        pallet: &str,
+        entry: &str,
+        keys: Vec<Vec<Value>>,
+    ) -> Result<Vec<Option<DecodedValue>>> {
+        let client = self.client();
+        let metadata = client.metadata();
+        let layout = EntryLayout::new(&metadata, pallet, entry)?;
+
+        let storage_keys = keys
+            .into_iter()
+            .map(|keys| {
+                client.storage()
+                    .address_bytes(&subxt::dynamic::storage(pallet, entry, keys))
+                    .map_err(|e| SDKError::Query(format!("Failed to encode storage key: {}", e)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut values = self.query_storage(&storage_keys, self.storage_block().await?).await?;
+        storage_keys
+            .iter()
+            .map(|key| {
+                values.remove(key)
+                    .flatten()
+                    .map(|bytes| DecodedValue::decode(&mut &bytes[..], layout.value_ty, &metadata))
+                    .transpose()
+            })
+            .collect()
+    }
+
+    /// Fetch one page of a storage map, starting after `start_key`
+    ///
+    /// `keys` are leading map keys restricting the iteration, e.g. the first key of
+    /// a double map; empty to iterate the whole map.
+    pub async fn storage_page(
+        &self,
+        pallet: &str,
+        entry: &str,
+        keys: Vec<Value>,
+        start_key: Option<&[u8]>,
+        page_size: u32,
+    ) -> Result<StoragePage> {
+        let block = self.storage_block().await?;
+        self.storage_page_at(pallet, entry, keys, start_key, page_size, block).await
+    }
+
+    /// Fetch every entry of a storage map under the given leading keys
+    ///
+    /// Pages are read from the same block, so the result is a consistent snapshot.
+    pub async fn storage_iter(&self, pallet: &str, entry: &str, keys: Vec<Value>) -> Result<Vec<StorageEntry>> {
+        let block = self.storage_block().await?;
+        let mut entries = Vec::new();
+        let mut start_key: Option<Vec<u8>> = None;
+
+        loop {
+            let page = self
+                .storage_page_at(pallet, entry, keys.clone(), start_key.as_deref(), DEFAULT_PAGE_SIZE, block)
+                .await?;
+            entries.extend(page.entries);
+
+            match page.next_key {
+                Some(next_key) => start_key = Some(next_key),
+                None => return Ok(entries),
+            }
+        }
+    }
+
+    async fn storage_page_at(
+        &self,
+        pallet: &str,
+        entry: &str,
+        keys: Vec<Value>,
+        start_key: Option<&[u8]>,
+        page_size: u32,
+        block: BlockHash,
+    ) -> Result<StoragePage> {
+        let client = self.client();
+        let metadata = client.metadata();
+        let layout = EntryLayout::new(&metadata, pallet, entry)?;
+
+        let prefix = client.storage()
+            .address_bytes(&subxt::dynamic::storage(pallet, entry, keys))
+            .map_err(|e| SDKError::Query(format!("Failed to encode storage key: {}", e)))?;
+
+        let page = client.rpc()
+            .storage_keys_paged(&prefix, page_size, start_key, Some(block))
+            .await
+            .map_err(|e| SDKError::Query(format!("Failed to list {}::{} keys: {}", pallet, entry, e)))?
+            .into_iter()
+            .map(|key| key.0)
+            .collect::<Vec<_>>();
+
+        let mut values = self.query_storage(&page, block).await?;
+        let mut entries = Vec::with_capacity(page.len());
+        for key in &page {
+            if let Some(bytes) = values.remove(key).flatten() {
+                entries.push(StorageEntry {
+                    key: key.clone(),
+                    keys: layout.decode_keys(key, &metadata),
+                    value: DecodedValue::decode(&mut &bytes[..], layout.value_ty, &metadata)?,
+                });
+            }
+        }
+
+        Ok(StoragePage { entries, next_key: next_key(&page, page_size) })
+    }
+
+    /// Raw values of storage keys at a block, keyed by storage key
+    async fn query_storage(&self, keys: &[Vec<u8>], block: BlockHash) -> Result<HashMap<Vec<u8>, Option<Vec<u8>>>> {
+        if keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let change_sets = self.client().rpc()
+            .query_storage_at(keys.iter().map(|key| &key[..]), Some(block))
+            .await
+            .map_err(|e| SDKError::Query(format!("Failed to query storage: {}", e)))?;
+
+        Ok(change_sets
+            .into_iter()
+            .flat_map(|change_set| change_set.changes)
+            .map(|(key, data)| (key.0, data.map(|data| data.0)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::substrate::value::test_util;
+    use scale_codec::Encode;
+    use scale_info::meta_type;
+    use sp_core::hashing::{blake2_128, twox_64};
+    use subxt::ext::frame_metadata::v15;
+
+    #[derive(scale_info::TypeInfo)]
+    #[allow(dead_code)]
+    struct AccountId32([u8; 32]);
+
+    /// Metadata with a `Staking::ErasStakers` double map and a `Staking::CurrentEra` value
+    fn staking_metadata() -> subxt::Metadata {
+        let entry = |name, ty| v15::StorageEntryMetadata {
+            name,
+            modifier: v15::StorageEntryModifier::Optional,
+            ty,
+            default: Vec::new(),
+            docs: Vec::new(),
+        };
+        let mut staking = test_util::pallet("Staking", 7, None, None);
+        staking.storage = Some(v15::PalletStorageMetadata {
+            prefix: "Staking",
+            entries: vec![
+                entry("CurrentEra", v15::StorageEntryType::Plain(meta_type::<u32>())),
+                entry("ErasStakers", v15::StorageEntryType::Map {
+                    hashers: vec![v15::StorageHasher::Twox64Concat, v15::StorageHasher::Blake2_128Concat],
+                    key: meta_type::<(u32, AccountId32)>(),
+                    value: meta_type::<u128>(),
+                }),
+            ],
+        });
+        test_util::metadata(vec![staking])
+    }
+
+    /// Full storage key of `ErasStakers(era, account)`
+    fn stakers_key(era: u32, account: u8) -> Vec<u8> {
+        let mut key = vec![0u8; STORAGE_PREFIX_LEN];
+        key.extend(twox_64(&era.encode()));
+        key.extend(era.encode());
+        key.extend(blake2_128(&[account; 32]));
+        key.extend([account; 32]);
+        key
+    }
+
+    #[test]
+    fn test_entry_layout() {
+        let metadata = staking_metadata();
+
+        let plain = EntryLayout::new(&metadata, "Staking", "CurrentEra").unwrap();
+        assert!(plain.hashers.is_empty());
+        assert!(plain.key_types.is_empty());
+
+        let map = EntryLayout::new(&metadata, "Staking", "ErasStakers").unwrap();
+        assert!(matches!(
+            map.hashers[..],
+            [StorageHasher::Twox64Concat, StorageHasher::Blake2_128Concat]
+        ));
+        assert_eq!(map.key_types.len(), 2);
+
+        assert!(EntryLayout::new(&metadata, "Staking", "Missing").is_err());
+        assert!(EntryLayout::new(&metadata, "Missing", "ErasStakers").is_err());
+        println!("✅ Entry layout test passed");
+    }
+
+    #[test]
+    fn test_decode_keys() {
+        let metadata = staking_metadata();
+        let layout = EntryLayout::new(&metadata, "Staking", "ErasStakers").unwrap();
+
+        let key = stakers_key(42, 1);
+        assert_eq!(layout.decode_keys(&key, &metadata), vec![DecodedValue::U128(42), test_util::account(1)]);
+
+        // A truncated key only yields the keys it fully contains
+        let truncated = &key[..STORAGE_PREFIX_LEN + 8 + 4 + 8];
+        assert_eq!(layout.decode_keys(truncated, &metadata), vec![DecodedValue::U128(42)]);
+        assert!(layout.decode_keys(&key[..STORAGE_PREFIX_LEN], &metadata).is_empty());
+        println!("✅ Decode keys test passed");
+    }
+
+    #[test]
+    fn test_next_key() {
+        let page = vec![stakers_key(1, 1), stakers_key(1, 2)];
+
+        // A full page continues after its last key
+        assert_eq!(next_key(&page, 2), Some(stakers_key(1, 2)));
+        // A short page is the last one
+        assert_eq!(next_key(&page, 3), None);
+        assert_eq!(next_key(&[], DEFAULT_PAGE_SIZE), None);
+        println!("✅ Next key test passed");
+    }
+}
@@ -0,0 +1,264 @@
+//! Treasury proposals, spends and pot information backed by `pallet_treasury`
+
+use crate::substrate::calls::Call;
+use crate::substrate::client::{account_value, pallet_account, Connection, SignedConnection};
+use crate::substrate::types::{Balance, TxResult};
+use crate::substrate::value::DecodedValue;
+use crate::types::{Result, SDKError};
+use serde::{Deserialize, Serialize};
+use sp_runtime::AccountId32;
+use subxt::dynamic::Value;
+
+/// Pallet id used when the runtime does not expose `Treasury::PalletId`
+const DEFAULT_TREASURY_PALLET_ID: [u8; 8] = *b"py/trsry";
+
+/// Spend proposal, from `Treasury::Proposals`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TreasuryProposal {
+    /// Proposal index
+    pub id: u32,
+    /// Account that made the proposal
+    pub proposer: AccountId32,
+    /// Account to be paid
+    pub beneficiary: AccountId32,
+    /// Amount to be paid
+    pub value: Balance,
+    /// Bond reserved from the proposer
+    pub bond: Balance,
+    /// Whether the proposal is approved and awaiting payout
+    pub approved: bool,
+}
+
+impl TreasuryProposal {
+    fn from_value(id: u32, value: &DecodedValue, approvals: &[u32]) -> Option<Self> {
+        Some(Self {
+            id,
+            proposer: value.field("proposer")?.as_account_id()?,
+            beneficiary: value.field("beneficiary")?.as_account_id()?,
+            value: value.field("value")?.as_u128()?,
+            bond: value.field("bond")?.as_u128()?,
+            approved: approvals.contains(&id),
+        })
+    }
+}
+
+/// Payment state of a spend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaymentState {
+    /// Not paid out yet
+    Pending,
+    /// Payment started, settle it with `check_status`
+    Attempted,
+    /// Payment failed, it can be retried with `payout`
+    Failed,
+}
+
+/// Approved spend, from `Treasury::Spends`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreasurySpend {
+    /// Spend index
+    pub id: u32,
+    /// Asset paid, as defined by the runtime's `AssetKind`
+    pub asset_kind: DecodedValue,
+    /// Amount to be paid
+    pub amount: Balance,
+    /// Beneficiary, as defined by the runtime's `Beneficiary`
+    pub beneficiary: DecodedValue,
+    /// Block from which the spend can be paid out
+    pub valid_from: u32,
+    /// Block after which the spend expires
+    pub expire_at: u32,
+    /// Payment state
+    pub status: PaymentState,
+}
+
+impl TreasurySpend {
+    fn from_value(id: u32, value: &DecodedValue) -> Option<Self> {
+        let status = match value.field("status")?.variant_name()? {
+            "Pending" => PaymentState::Pending,
+            "Attempted" => PaymentState::Attempted,
+            "Failed" => PaymentState::Failed,
+            _ => return None,
+        };
+
+        Some(Self {
+            id,
+            asset_kind: value.field("asset_kind")?.clone(),
+            amount: value.field("amount")?.as_u128()?,
+            beneficiary: value.field("beneficiary")?.clone(),
+            valid_from: value.field("valid_from")?.as_u128()? as u32,
+            expire_at: value.field("expire_at")?.as_u128()? as u32,
+            status,
+        })
+    }
+
+    /// Beneficiary account, if the runtime pays plain accounts
+    pub fn beneficiary_account(&self) -> Option<AccountId32> {
+        self.beneficiary.as_account_id()
+    }
+
+    /// Whether the spend can be paid out at block `now`
+    pub fn is_payable(&self, now: u32) -> bool {
+        self.status != PaymentState::Attempted && self.valid_from <= now && now < self.expire_at
+    }
+}
+
+/// Treasury pot and spend period, for dashboards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreasuryInfo {
+    /// Treasury account
+    pub account: AccountId32,
+    /// Funds available for spending, the free balance above the existential deposit
+    pub pot: Balance,
+    /// Blocks between spend periods
+    pub spend_period: u32,
+    /// Share of the pot burnt at each spend period when not spent, in parts per million
+    pub burn: u32,
+    /// Block at which the next spend period starts
+    pub next_spend_period: u32,
+    /// Number of proposals ever made
+    pub proposal_count: u32,
+    /// Approved proposals awaiting payout
+    pub approvals: Vec<u32>,
+}
+
+impl TreasuryInfo {
+    /// Amount burnt at the next spend period if the pot is not spent
+    pub fn projected_burn(&self) -> Balance {
+        sp_runtime::Permill::from_parts(self.burn).mul_floor(self.pot)
+    }
+}
+
+impl Connection {
+    /// Treasury account, holding the pot
+    pub fn treasury_account(&self) -> AccountId32 {
+        pallet_account(self.pallet_id("Treasury", DEFAULT_TREASURY_PALLET_ID), ())
+    }
+
+    /// Every open spend proposal with its amounts, ordered by index
+    pub async fn get_treasury_proposals(&self) -> Result<Vec<TreasuryProposal>> {
+        let approvals = self.get_treasury_approvals().await?;
+        let mut proposals: Vec<_> = self.storage_iter("Treasury", "Proposals", vec![])
+            .await?
+            .iter()
+            .filter_map(|entry| {
+                let id = entry.keys.first()?.as_u128()? as u32;
+                TreasuryProposal::from_value(id, &entry.value, &approvals)
+            })
+            .collect();
+        proposals.sort_by_key(|proposal| proposal.id);
+        Ok(proposals)
+    }
+
+    /// A spend proposal, `None` if it does not exist or was paid out
+    pub async fn get_treasury_proposal(&self, id: u32) -> Result<Option<TreasuryProposal>> {
+        match self.storage_fetch("Treasury", "Proposals", vec![Value::u128(id as u128)]).await? {
+            Some(proposal) => TreasuryProposal::from_value(id, &proposal, &self.get_treasury_approvals().await?)
+                .map(Some)
+                .ok_or_else(|| SDKError::Query("Invalid Proposals storage value".to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Every approved spend not yet paid out, ordered by index
+    pub async fn get_treasury_spends(&self) -> Result<Vec<TreasurySpend>> {
+        let mut spends: Vec<_> = self.storage_iter("Treasury", "Spends", vec![])
+            .await?
+            .iter()
+            .filter_map(|entry| TreasurySpend::from_value(entry.keys.first()?.as_u128()? as u32, &entry.value))
+            .collect();
+        spends.sort_by_key(|spend| spend.id);
+        Ok(spends)
+    }
+
+    /// An approved spend, `None` if it does not exist or was settled
+    pub async fn get_treasury_spend(&self, id: u32) -> Result<Option<TreasurySpend>> {
+        match self.storage_fetch("Treasury", "Spends", vec![Value::u128(id as u128)]).await? {
+            Some(spend) => TreasurySpend::from_value(id, &spend)
+                .map(Some)
+                .ok_or_else(|| SDKError::Query("Invalid Spends storage value".to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Pot, spend period and burn of the treasury
+    pub async fn get_treasury_info(&self) -> Result<TreasuryInfo> {
+        let account = self.treasury_account();
+        let free = self.storage_fetch_or_default("System", "Account", vec![account_value(&account)])
+            .await?
+            .field("data")
+            .and_then(|data| data.field("free"))
+            .and_then(|free| free.as_u128())
+            .unwrap_or(0);
+        let existential_deposit = self.get_existential_deposit().await?;
+        let spend_period = self.integer_constant("Treasury", "SpendPeriod").await? as u32;
+        let now = self.storage_fetch_or_default("System", "Number", vec![]).await?.as_u128().unwrap_or(0) as u32;
+
+        Ok(TreasuryInfo {
+            account,
+            pot: free.saturating_sub(existential_deposit),
+            spend_period,
+            burn: self.integer_constant("Treasury", "Burn").await? as u32,
+            next_spend_period: match spend_period {
+                0 => now,
+                period => (now / period + 1) * period,
+            },
+            proposal_count: self.get_treasury_proposals_count().await?,
+            approvals: self.get_treasury_approvals().await?,
+        })
+    }
+}
+
+impl SignedConnection {
+    /// Pay out an approved spend once it is valid
+    pub async fn treasury_payout(&self, index: u32) -> Result<TxResult> {
+        self.submit(Call::treasury_payout(index)).await
+    }
+
+    /// Settle the payment state of a paid out spend, removing it once paid
+    pub async fn treasury_check_status(&self, index: u32) -> Result<TxResult> {
+        self.submit(Call::treasury_check_status(index)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::substrate::value::test_util::{account, named};
+
+    #[test]
+    fn test_treasury_proposal_from_value() {
+        let value = named(vec![
+            ("proposer", account(1)),
+            ("value", DecodedValue::U128(1_000)),
+            ("beneficiary", account(2)),
+            ("bond", DecodedValue::U128(50)),
+        ]);
+
+        let proposal = TreasuryProposal::from_value(4, &value, &[4, 9]).unwrap();
+        assert_eq!(proposal.beneficiary, AccountId32::from([2u8; 32]));
+        assert_eq!(proposal.value, 1_000);
+        assert_eq!(proposal.bond, 50);
+        assert!(proposal.approved);
+        println!("✅ Treasury proposal decoding test passed");
+    }
+
+    #[test]
+    fn test_treasury_spend_from_value() {
+        let value = named(vec![
+            ("asset_kind", DecodedValue::Unnamed(vec![])),
+            ("amount", DecodedValue::U128(500)),
+            ("beneficiary", account(3)),
+            ("valid_from", DecodedValue::U128(100)),
+            ("expire_at", DecodedValue::U128(200)),
+            ("status", DecodedValue::Variant("Pending".to_string(), Box::default())),
+        ]);
+
+        let spend = TreasurySpend::from_value(0, &value).unwrap();
+        assert_eq!(spend.beneficiary_account(), Some(AccountId32::from([3u8; 32])));
+        assert!(!spend.is_payable(99));
+        assert!(spend.is_payable(150));
+        assert!(!spend.is_payable(200));
+        println!("✅ Treasury spend decoding test passed");
+    }
+}
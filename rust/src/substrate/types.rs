@@ -1,6 +1,9 @@
 //! Type definitions for Substrate interactions
 
+use crate::substrate::events::RawEvent;
+use crate::substrate::value::DecodedValue;
 use sp_core::{crypto::AccountId32, H256};
+use scale_codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -10,63 +13,376 @@ pub type Balance = u128;
 /// Account information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountInfo {
+    /// Number of transactions sent by the account
     pub nonce: u32,
+    /// Number of modules depending on the account
     pub consumers: u32,
+    /// Number of modules allowing the account to exist
     pub providers: u32,
+    /// Number of modules allowing the account to exist without a provider
     pub sufficients: u32,
+    /// Balance data of the account
     pub data: AccountData,
+    /// Locks on the balance, from `Balances::Locks`
+    pub locks: Vec<BalanceLock>,
+    /// Freezes on the balance, from `Balances::Freezes`
+    pub freezes: Vec<BalanceFreeze>,
+}
+
+impl AccountInfo {
+    /// Amount of the free balance that can be transferred
+    ///
+    /// An account other pallets depend on (`consumers > 0`) cannot be reaped, so
+    /// the existential deposit is kept even without `keep_alive`.
+    pub fn transferable(&self, existential_deposit: Balance, keep_alive: bool) -> Balance {
+        self.data.transferable(existential_deposit, keep_alive || self.consumers > 0)
+    }
+
+    /// Why the balance is locked: lock ids and freeze reasons, with their amounts
+    pub fn lock_reasons(&self) -> Vec<(String, Balance)> {
+        self.locks
+            .iter()
+            .map(|lock| (lock.id_str(), lock.amount))
+            .chain(self.freezes.iter().map(|freeze| (freeze.id.clone(), freeze.amount)))
+            .collect()
+    }
 }
 
 /// Account data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AccountData {
+    /// Balance not reserved, part of it may be frozen
     pub free: Balance,
+    /// Balance reserved by pallets, e.g. for deposits
     pub reserved: Balance,
-    pub free_frozen: Balance,
-    pub reserved_frozen: Balance,
+    /// Amount of the balance that cannot be spent, locks and freezes included
+    ///
+    /// Frozen funds may overlap with reserved ones, so only the part above
+    /// `reserved` restricts the free balance.
+    pub frozen: Balance,
+    /// Extra flags of the account data
     pub flags: u128,
 }
 
+impl AccountData {
+    /// Decode an `AccountData` value
+    ///
+    /// Runtimes before the fungible traits migration split the frozen amount
+    /// into `misc_frozen` and `fee_frozen`; the larger of the two is used.
+    pub(crate) fn from_value(value: &DecodedValue) -> Option<Self> {
+        let amount = |name: &str| value.field(name).and_then(|amount| amount.as_u128());
+        Some(Self {
+            free: amount("free")?,
+            reserved: amount("reserved")?,
+            frozen: match amount("frozen") {
+                Some(frozen) => frozen,
+                None => amount("misc_frozen")?.max(amount("fee_frozen")?),
+            },
+            flags: amount("flags").unwrap_or(0),
+        })
+    }
+
+    /// Free plus reserved balance
+    pub fn total(&self) -> Balance {
+        self.free.saturating_add(self.reserved)
+    }
+
+    /// Amount of the free balance that can be transferred
+    ///
+    /// With `keep_alive`, at least `existential_deposit` stays in the account so
+    /// it is not reaped.
+    pub fn transferable(&self, existential_deposit: Balance, keep_alive: bool) -> Balance {
+        let untouchable = self.frozen.saturating_sub(self.reserved);
+        let untouchable = if keep_alive { untouchable.max(existential_deposit) } else { untouchable };
+        self.free.saturating_sub(untouchable)
+    }
+}
+
+/// Balance lock, from `Balances::Locks`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceLock {
+    /// Lock identifier, e.g. `democrac` or `staking `
+    pub id: [u8; 8],
+    /// Amount locked
+    pub amount: Balance,
+    /// Operations the lock applies to: `Fee`, `Misc` or `All`
+    pub reasons: String,
+}
+
+impl BalanceLock {
+    /// Lock identifier as text, without trailing padding
+    pub fn id_str(&self) -> String {
+        String::from_utf8_lossy(&self.id).trim_end().to_string()
+    }
+
+    /// Decode a `BalanceLock` value
+    pub(crate) fn from_value(value: &DecodedValue) -> Option<Self> {
+        Some(Self {
+            id: value.field("id")?.as_bytes()?.try_into().ok()?,
+            amount: value.field("amount")?.as_u128()?,
+            reasons: value.field("reasons")?.variant_name()?.to_string(),
+        })
+    }
+}
+
+/// Balance freeze, from `Balances::Freezes`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceFreeze {
+    /// Freeze reason as `Pallet::Reason`, e.g. `NominationPools::PoolMinBalance`
+    pub id: String,
+    /// Amount frozen
+    pub amount: Balance,
+}
+
+impl BalanceFreeze {
+    /// Decode an `IdAmount` value of a freeze
+    pub(crate) fn from_value(value: &DecodedValue) -> Option<Self> {
+        let id = value.field("id")?;
+        let pallet = id.variant_name()?;
+        Some(Self {
+            id: match id.at(0).and_then(|reason| reason.variant_name()) {
+                Some(reason) => format!("{}::{}", pallet, reason),
+                None => pallet.to_string(),
+            },
+            amount: value.field("amount")?.as_u128()?,
+        })
+    }
+}
+
 /// Block information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockInfo {
+    /// Block hash
     pub hash: H256,
+    /// Block number
     pub number: u64,
+    /// Hash of the parent block
     pub parent_hash: H256,
+    /// Root of the state trie
     pub state_root: H256,
+    /// Root of the extrinsics trie
     pub extrinsics_root: H256,
 }
 
 /// Chain information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChainInfo {
+    /// Chain name reported by the node
     pub chain_name: String,
+    /// Runtime spec version
     pub version: u32,
+    /// Chain properties, e.g. token symbol and decimals
     pub properties: HashMap<String, String>,
 }
 
 /// Staking information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StakingInfo {
+    /// Controller of the account, if bonded
     pub bonded: Option<AccountId32>,
+    /// Minimum number of validators
     pub minimum_validator_count: u32,
+    /// Number of sessions per era
     pub sessions_per_era: u32,
 }
 
 /// Transaction status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TransactionStatus {
+    /// Submitted, not in a block yet
     Pending,
-    InBlock { block_hash: H256 },
-    Finalized { block_hash: H256 },
+    /// Included in a block
+    InBlock {
+        /// Hash of the including block
+        block_hash: H256,
+    },
+    /// Included in a finalized block
+    Finalized {
+        /// Hash of the finalized block
+        block_hash: H256,
+    },
+    /// Submission failed
     Error(String),
 }
 
-/// Metadata version information
+/// Outcome of a submitted extrinsic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxResult {
+    /// Extrinsic hash
+    pub tx_hash: H256,
+    /// Inclusion status reached before returning
+    pub status: TransactionStatus,
+    /// Index of the extrinsic in its block
+    pub extrinsic_index: Option<u32>,
+    /// Fee actually paid, from `TransactionPayment::TransactionFeePaid`
+    pub fee: Option<Balance>,
+    /// Events emitted by the extrinsic
+    pub events: Vec<RawEvent>,
+    /// Error of a failed dispatch, from `System::ExtrinsicFailed`
+    pub dispatch_error: Option<DispatchError>,
+}
+
+impl TxResult {
+    /// Result of an extrinsic that was only submitted to the pool
+    pub(crate) fn submitted(tx_hash: H256) -> Self {
+        Self {
+            tx_hash,
+            status: TransactionStatus::Pending,
+            extrinsic_index: None,
+            fee: None,
+            events: Vec::new(),
+            dispatch_error: None,
+        }
+    }
+
+    /// Hash of the block including the extrinsic
+    pub fn block_hash(&self) -> Option<H256> {
+        match self.status {
+            TransactionStatus::InBlock { block_hash } | TransactionStatus::Finalized { block_hash } => Some(block_hash),
+            _ => None,
+        }
+    }
+
+    /// Whether the extrinsic was included and dispatched without error
+    pub fn is_success(&self) -> bool {
+        self.block_hash().is_some() && self.dispatch_error.is_none()
+    }
+
+    /// Events emitted by the extrinsic with the given pallet and name
+    pub fn find_events<'a>(&'a self, pallet: &'a str, name: &'a str) -> impl Iterator<Item = &'a RawEvent> + 'a {
+        self.events.iter().filter(move |event| event.pallet == pallet && event.name == name)
+    }
+}
+
+/// Decoded dispatch error of a failed extrinsic
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DispatchError {
+    /// Error raised by a pallet
+    Module {
+        /// Pallet name
+        pallet: String,
+        /// Error variant name
+        error: String,
+        /// Error documentation from the metadata
+        docs: String,
+    },
+    /// Any other dispatch error, e.g. `BadOrigin` or `Token::FundsUnavailable`
+    Other(String),
+}
+
+impl DispatchError {
+    /// Decode a `sp_runtime::DispatchError` value, resolving module errors via the metadata
+    pub(crate) fn from_value(value: &DecodedValue, metadata: &subxt::Metadata) -> Self {
+        let kind = value.variant_name().unwrap_or("Unknown");
+        if kind != "Module" {
+            return match value.at(0).and_then(|inner| inner.variant_name()) {
+                Some(inner) => DispatchError::Other(format!("{}::{}", kind, inner)),
+                None => DispatchError::Other(kind.to_string()),
+            };
+        }
+
+        let module = value.at(0);
+        let pallet_index = module.and_then(|m| m.field("index")).and_then(|i| i.as_u128());
+        let error_index = module
+            .and_then(|m| m.field("error"))
+            .and_then(|e| e.as_bytes())
+            .and_then(|bytes| bytes.first().copied());
+
+        Self::module(pallet_index.map(|index| index as u8), error_index, metadata)
+    }
+
+    /// Convert a SCALE decoded `sp_runtime::DispatchError`, resolving module errors via the metadata
+    pub(crate) fn from_runtime(error: &sp_runtime::DispatchError, metadata: &subxt::Metadata) -> Self {
+        match error {
+            sp_runtime::DispatchError::Module(module) => Self::module(Some(module.index), Some(module.error[0]), metadata),
+            other => DispatchError::Other(format!("{:?}", other)),
+        }
+    }
+
+    /// Module error named after its pallet and variant in the metadata
+    fn module(pallet_index: Option<u8>, error_index: Option<u8>, metadata: &subxt::Metadata) -> Self {
+        let pallet = pallet_index.and_then(|index| metadata.pallet_by_index(index));
+        let variant = pallet.as_ref()
+            .zip(error_index)
+            .and_then(|(pallet, index)| pallet.error_variant_by_index(index));
+
+        match (pallet.as_ref(), variant) {
+            (Some(pallet), Some(variant)) => DispatchError::Module {
+                pallet: pallet.name().to_string(),
+                error: variant.name.clone(),
+                docs: variant.docs.join(" "),
+            },
+            _ => DispatchError::Other(format!(
+                "Module {{ index: {:?}, error: {:?} }}",
+                pallet_index, error_index
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for DispatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DispatchError::Module { pallet, error, .. } => write!(f, "{}::{}", pallet, error),
+            DispatchError::Other(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// Two-dimensional weight: execution time and proof size
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub struct Weight {
+    /// Execution time in picoseconds
+    #[codec(compact)]
+    pub ref_time: u64,
+    /// Size of the storage proof in bytes
+    #[codec(compact)]
+    pub proof_size: u64,
+}
+
+impl Weight {
+    /// Create a weight from its two components
+    pub fn new(ref_time: u64, proof_size: u64) -> Self {
+        Self { ref_time, proof_size }
+    }
+}
+
+/// Dispatch class of an extrinsic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub enum DispatchClass {
+    /// Regular transaction
+    Normal,
+    /// Operational transaction, may use reserved block space
+    Operational,
+    /// Inherent that must be included in the block
+    Mandatory,
+}
+
+/// Fee estimate of an extrinsic, from `TransactionPaymentApi`
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeEstimate {
+    /// Weight of the call
+    pub weight: Weight,
+    /// Dispatch class of the call
+    pub class: DispatchClass,
+    /// Fee charged before the tip, equal to the sum of the fee parts
+    pub partial_fee: Balance,
+    /// Base fee charged for every extrinsic
+    pub base_fee: Balance,
+    /// Fee for the encoded length of the extrinsic
+    pub len_fee: Balance,
+    /// Weight fee, adjusted by the current fee multiplier
+    pub adjusted_weight_fee: Balance,
+}
+
+/// Metadata version information
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MetadataVersion {
+    /// Runtime spec version
     pub spec_version: u32,
+    /// Transaction format version
     pub transaction_version: u32,
+    /// Metadata format version
     pub metadata_version: u32,
 }
 // ============================================================================
@@ -76,3 +392,66 @@ pub struct MetadataVersion {
 // Note: Direct trait implementations for external types (sp_core::crypto::AccountId32,
 // selendra_client types) are orphan implementations and not allowed.
 // Use conversion helper functions in the substrate client module instead.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::substrate::value::test_util::named;
+
+    #[test]
+    fn test_tx_result_success() {
+        let block_hash = H256::repeat_byte(1);
+        let mut result = TxResult::submitted(H256::zero());
+        assert!(!result.is_success());
+
+        result.status = TransactionStatus::InBlock { block_hash };
+        assert_eq!(result.block_hash(), Some(block_hash));
+        assert!(result.is_success());
+
+        result.dispatch_error = Some(DispatchError::Module {
+            pallet: "Balances".to_string(),
+            error: "InsufficientBalance".to_string(),
+            docs: String::new(),
+        });
+        assert!(!result.is_success());
+        assert_eq!(result.dispatch_error.unwrap().to_string(), "Balances::InsufficientBalance");
+        println!("✅ TxResult success test passed");
+    }
+
+    #[test]
+    fn test_balance_freeze_from_value() {
+        let reason = DecodedValue::Variant("PoolMinBalance".to_string(), Box::default());
+        let value = named(vec![
+            ("id", DecodedValue::Variant("NominationPools".to_string(), Box::new(DecodedValue::Unnamed(vec![reason])))),
+            ("amount", DecodedValue::U128(100)),
+        ]);
+
+        let freeze = BalanceFreeze::from_value(&value).unwrap();
+        assert_eq!(freeze.id, "NominationPools::PoolMinBalance");
+        assert_eq!(freeze.amount, 100);
+        println!("✅ Balance freeze decoding test passed");
+    }
+
+    #[test]
+    fn test_account_data_transferable() {
+        let legacy = named(vec![
+            ("free", DecodedValue::U128(1_000)),
+            ("reserved", DecodedValue::U128(200)),
+            ("misc_frozen", DecodedValue::U128(500)),
+            ("fee_frozen", DecodedValue::U128(300)),
+        ]);
+
+        let data = AccountData::from_value(&legacy).unwrap();
+        assert_eq!(data.frozen, 500);
+        assert_eq!(data.total(), 1_200);
+        // Frozen funds above the reserved balance stay in the account
+        assert_eq!(data.transferable(100, false), 700);
+        assert_eq!(data.transferable(100, true), 700);
+        assert_eq!(data.transferable(400, true), 600);
+
+        let unfrozen = AccountData { free: 1_000, ..Default::default() };
+        assert_eq!(unfrozen.transferable(100, false), 1_000);
+        assert_eq!(unfrozen.transferable(100, true), 900);
+        println!("✅ Account data transferable test passed");
+    }
+}
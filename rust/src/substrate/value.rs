@@ -0,0 +1,447 @@
+//! Dynamic value tree for data decoded against runtime metadata
+
+use crate::types::{Result, SDKError};
+use serde::{Deserialize, Serialize};
+use sp_runtime::AccountId32;
+use subxt::ext::scale_value::{self, Composite, Primitive, ValueDef};
+
+/// A SCALE value decoded using type information from the runtime metadata
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DecodedValue {
+    /// Struct-like value with named fields
+    Named(Vec<(String, DecodedValue)>),
+    /// Tuple, array or sequence of values
+    Unnamed(Vec<DecodedValue>),
+    /// Enum variant with its fields
+    Variant(String, Box<DecodedValue>),
+    /// Boolean
+    Bool(bool),
+    /// Character
+    Char(char),
+    /// String
+    String(String),
+    /// Unsigned integer up to 128 bits
+    U128(u128),
+    /// Signed integer up to 128 bits
+    I128(i128),
+    /// Unsigned 256 bit integer, little endian bytes
+    U256([u8; 32]),
+    /// Signed 256 bit integer, little endian bytes
+    I256([u8; 32]),
+    /// Bit sequence
+    Bits(Vec<bool>),
+}
+
+impl DecodedValue {
+    /// Decode SCALE bytes as the given metadata type
+    pub fn decode(bytes: &mut &[u8], type_id: u32, metadata: &subxt::Metadata) -> Result<Self> {
+        scale_value::scale::decode_as_type(bytes, type_id, metadata.types())
+            .map(Self::from)
+            .map_err(|e| SDKError::SerializationError(format!("Failed to decode value: {}", e)))
+    }
+
+    /// Get a named field of a struct or struct-like variant
+    pub fn field(&self, name: &str) -> Option<&DecodedValue> {
+        match self {
+            DecodedValue::Named(fields) => fields.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+            DecodedValue::Variant(_, fields) => fields.field(name),
+            _ => None,
+        }
+    }
+
+    /// Get a positional field of a tuple, sequence or tuple-like variant
+    pub fn at(&self, index: usize) -> Option<&DecodedValue> {
+        match self {
+            DecodedValue::Unnamed(values) => values.get(index),
+            DecodedValue::Named(fields) => fields.get(index).map(|(_, v)| v),
+            DecodedValue::Variant(_, fields) => fields.at(index),
+            _ => None,
+        }
+    }
+
+    /// Items of a sequence, or the fields of a composite in order
+    pub fn items(&self) -> Vec<&DecodedValue> {
+        match self {
+            DecodedValue::Unnamed(values) => values.iter().collect(),
+            DecodedValue::Named(fields) => fields.iter().map(|(_, v)| v).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Name of the variant, if this is an enum value
+    pub fn variant_name(&self) -> Option<&str> {
+        match self {
+            DecodedValue::Variant(name, _) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Unwrap single-field newtypes such as `Perbill(u32)` or `AccountId32([u8; 32])`
+    fn inner(&self) -> &DecodedValue {
+        match self {
+            DecodedValue::Unnamed(values) if values.len() == 1 => values[0].inner(),
+            DecodedValue::Named(fields) if fields.len() == 1 => fields[0].1.inner(),
+            other => other,
+        }
+    }
+
+    /// Interpret the value as an unsigned integer
+    pub fn as_u128(&self) -> Option<u128> {
+        match self.inner() {
+            DecodedValue::U128(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Interpret the value as a boolean
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.inner() {
+            DecodedValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Interpret the value as a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self.inner() {
+            DecodedValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Interpret the value as a byte sequence
+    ///
+    /// A one byte sequence looks like a newtype, so the elements of the value itself
+    /// are read before unwrapping newtypes such as `BoundedVec<u8>`.
+    pub fn as_bytes(&self) -> Option<Vec<u8>> {
+        match self {
+            DecodedValue::Unnamed(values) => values
+                .iter()
+                .map(|v| match v {
+                    DecodedValue::U128(n) => u8::try_from(*n).ok(),
+                    _ => None,
+                })
+                .collect::<Option<Vec<u8>>>()
+                .or_else(|| match values.as_slice() {
+                    [single] => single.as_bytes(),
+                    _ => None,
+                }),
+            DecodedValue::Named(fields) if fields.len() == 1 => fields[0].1.as_bytes(),
+            _ => None,
+        }
+    }
+
+    /// Interpret the value as a 32 byte account id
+    pub fn as_account_id(&self) -> Option<AccountId32> {
+        let bytes: [u8; 32] = self.as_bytes()?.try_into().ok()?;
+        Some(AccountId32::from(bytes))
+    }
+
+    /// Interpret the value as a sequence of account ids, unwrapping newtypes such as `BoundedVec`
+    pub fn as_account_ids(&self) -> Vec<AccountId32> {
+        match self.items().as_slice() {
+            [single] if single.as_account_id().is_none() => single.as_account_ids(),
+            items => items.iter().filter_map(|item| item.as_account_id()).collect(),
+        }
+    }
+
+    /// Interpret the value as `Option<T>`, `None` for the `None` variant
+    pub fn as_option(&self) -> Option<&DecodedValue> {
+        match self {
+            DecodedValue::Variant(name, fields) if name == "Some" => fields.at(0),
+            _ => None,
+        }
+    }
+
+    /// Render the value as plain JSON
+    ///
+    /// Byte sequences become `0x` prefixed hex strings, integers that do not fit in
+    /// a JSON number become decimal strings and variants without fields become their name.
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::{json, Map, Value};
+
+        match self {
+            DecodedValue::Named(fields) => Value::Object(
+                fields.iter().map(|(name, value)| (name.clone(), value.to_json())).collect::<Map<_, _>>(),
+            ),
+            DecodedValue::Unnamed(values) => match self.as_bytes() {
+                Some(bytes) if !bytes.is_empty() && values.len() == bytes.len() => {
+                    json!(format!("0x{}", hex::encode(bytes)))
+                }
+                _ => Value::Array(values.iter().map(|v| v.to_json()).collect()),
+            },
+            DecodedValue::Variant(name, fields) => {
+                if fields.items().is_empty() {
+                    json!(name)
+                } else {
+                    let mut object = Map::new();
+                    object.insert(name.clone(), fields.to_json());
+                    Value::Object(object)
+                }
+            }
+            DecodedValue::Bool(b) => json!(b),
+            DecodedValue::Char(c) => json!(c.to_string()),
+            DecodedValue::String(s) => json!(s),
+            DecodedValue::U128(n) => match u64::try_from(*n) {
+                Ok(n) => json!(n),
+                Err(_) => json!(n.to_string()),
+            },
+            DecodedValue::I128(n) => match i64::try_from(*n) {
+                Ok(n) => json!(n),
+                Err(_) => json!(n.to_string()),
+            },
+            DecodedValue::U256(bytes) | DecodedValue::I256(bytes) => {
+                json!(format!("0x{}", hex::encode(bytes)))
+            }
+            DecodedValue::Bits(bits) => json!(bits),
+        }
+    }
+}
+
+/// Convert JSON into a dynamic value, e.g. for storage keys or call arguments
+///
+/// `0x` prefixed strings become bytes, SS58 addresses become account ids, numeric
+/// strings become integers, objects with a single capitalized key become enum
+/// variants (`{"Staked": null}` for a variant without fields) and `null` becomes `None`.
+pub fn value_from_json(json: &serde_json::Value) -> Result<scale_value::Value> {
+    use serde_json::Value as Json;
+    use sp_core::crypto::Ss58Codec;
+
+    Ok(match json {
+        Json::Null => scale_value::Value::unnamed_variant("None", []),
+        Json::Bool(b) => scale_value::Value::bool(*b),
+        Json::Number(n) => match (n.as_u64(), n.as_i64()) {
+            (Some(n), _) => scale_value::Value::u128(n as u128),
+            (None, Some(n)) => scale_value::Value::i128(n as i128),
+            _ => return Err(SDKError::Conversion(format!("Unsupported non-integer number {}", n))),
+        },
+        Json::String(s) => {
+            if let Some(hex) = s.strip_prefix("0x") {
+                let bytes = hex::decode(hex)
+                    .map_err(|e| SDKError::Conversion(format!("Invalid hex string {}: {}", s, e)))?;
+                scale_value::Value::from_bytes(bytes)
+            } else if let Ok(account) = AccountId32::from_ss58check(s) {
+                scale_value::Value::from_bytes(account)
+            } else if let Ok(n) = s.parse::<u128>() {
+                scale_value::Value::u128(n)
+            } else {
+                scale_value::Value::string(s.clone())
+            }
+        }
+        Json::Array(items) => scale_value::Value::unnamed_composite(
+            items.iter().map(value_from_json).collect::<Result<Vec<_>>>()?,
+        ),
+        Json::Object(fields) => {
+            let variant = fields.iter().next()
+                .filter(|(name, _)| fields.len() == 1 && name.starts_with(char::is_uppercase));
+            match variant {
+                Some((name, Json::Null)) => scale_value::Value::unnamed_variant(name.clone(), []),
+                Some((name, Json::Array(values))) => scale_value::Value::unnamed_variant(
+                    name.clone(),
+                    values.iter().map(value_from_json).collect::<Result<Vec<_>>>()?,
+                ),
+                Some((name, Json::Object(values))) => scale_value::Value::named_variant(
+                    name.clone(),
+                    values.iter()
+                        .map(|(field, value)| Ok((field.clone(), value_from_json(value)?)))
+                        .collect::<Result<Vec<_>>>()?,
+                ),
+                Some((name, value)) => scale_value::Value::unnamed_variant(name.clone(), [value_from_json(value)?]),
+                None => scale_value::Value::named_composite(
+                    fields.iter()
+                        .map(|(field, value)| Ok((field.clone(), value_from_json(value)?)))
+                        .collect::<Result<Vec<_>>>()?,
+                ),
+            }
+        }
+    })
+}
+
+/// Items of a `Vec` or of a `BoundedVec` newtype around it
+pub(crate) fn bounded_items(value: &DecodedValue) -> Vec<&DecodedValue> {
+    match value {
+        DecodedValue::Unnamed(values) if matches!(values.as_slice(), [DecodedValue::Unnamed(_)]) => values[0].items(),
+        _ => value.items(),
+    }
+}
+
+impl Default for DecodedValue {
+    fn default() -> Self {
+        DecodedValue::Unnamed(Vec::new())
+    }
+}
+
+impl<T> From<Composite<T>> for DecodedValue {
+    fn from(composite: Composite<T>) -> Self {
+        match composite {
+            Composite::Named(fields) => DecodedValue::Named(
+                fields.into_iter().map(|(name, value)| (name, value.into())).collect(),
+            ),
+            Composite::Unnamed(values) => {
+                DecodedValue::Unnamed(values.into_iter().map(Into::into).collect())
+            }
+        }
+    }
+}
+
+impl<T> From<scale_value::Value<T>> for DecodedValue {
+    fn from(value: scale_value::Value<T>) -> Self {
+        match value.value {
+            ValueDef::Composite(composite) => composite.into(),
+            ValueDef::Variant(variant) => {
+                DecodedValue::Variant(variant.name, Box::new(variant.values.into()))
+            }
+            ValueDef::BitSequence(bits) => DecodedValue::Bits(bits.iter().collect()),
+            ValueDef::Primitive(primitive) => match primitive {
+                Primitive::Bool(b) => DecodedValue::Bool(b),
+                Primitive::Char(c) => DecodedValue::Char(c),
+                Primitive::String(s) => DecodedValue::String(s),
+                Primitive::U128(n) => DecodedValue::U128(n),
+                Primitive::I128(n) => DecodedValue::I128(n),
+                Primitive::U256(bytes) => DecodedValue::U256(bytes),
+                Primitive::I256(bytes) => DecodedValue::I256(bytes),
+            },
+        }
+    }
+}
+
+/// Fixtures for tests decoding values and events
+#[cfg(test)]
+pub(crate) mod test_util {
+    use super::DecodedValue;
+    use scale_codec::{Decode, Encode};
+    use scale_info::MetaType;
+    use subxt::ext::frame_metadata::{v15, RuntimeMetadataPrefixed};
+
+    /// `AccountId32` value, a newtype around 32 bytes of `byte`
+    pub(crate) fn account(byte: u8) -> DecodedValue {
+        DecodedValue::Unnamed(vec![DecodedValue::Unnamed(vec![DecodedValue::U128(byte as u128); 32])])
+    }
+
+    /// Struct value with the given fields
+    pub(crate) fn named(fields: Vec<(&str, DecodedValue)>) -> DecodedValue {
+        DecodedValue::Named(fields.into_iter().map(|(name, value)| (name.to_string(), value)).collect())
+    }
+
+    /// `Option::Some` value
+    pub(crate) fn some(value: DecodedValue) -> DecodedValue {
+        DecodedValue::Variant("Some".to_string(), Box::new(DecodedValue::Unnamed(vec![value])))
+    }
+
+    /// Pallet declaring the given event and error enums
+    pub(crate) fn pallet(
+        name: &'static str,
+        index: u8,
+        event: Option<MetaType>,
+        error: Option<MetaType>,
+    ) -> v15::PalletMetadata {
+        v15::PalletMetadata {
+            name,
+            storage: None,
+            calls: None,
+            event: event.map(|ty| v15::PalletEventMetadata { ty }),
+            constants: Vec::new(),
+            error: error.map(|ty| v15::PalletErrorMetadata { ty }),
+            index,
+            docs: Vec::new(),
+        }
+    }
+
+    /// Runtime metadata made of `pallets` only
+    pub(crate) fn metadata(pallets: Vec<v15::PalletMetadata>) -> subxt::Metadata {
+        let unit = || MetaType::new::<()>();
+        let extrinsic = v15::ExtrinsicMetadata {
+            version: 4,
+            address_ty: unit(),
+            call_ty: unit(),
+            signature_ty: unit(),
+            extra_ty: unit(),
+            signed_extensions: Vec::new(),
+        };
+        let outer_enums = v15::OuterEnums {
+            call_enum_ty: unit(),
+            event_enum_ty: unit(),
+            error_enum_ty: unit(),
+        };
+        let custom = v15::CustomMetadata { map: Default::default() };
+
+        let metadata = v15::RuntimeMetadataV15::new(pallets, extrinsic, unit(), Vec::new(), outer_enums, custom);
+        let bytes = RuntimeMetadataPrefixed::from(metadata).encode();
+        subxt::Metadata::decode(&mut &bytes[..]).expect("fixture metadata decodes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::test_util::account;
+
+    #[test]
+    fn test_account_id_from_newtype() {
+        let bytes = DecodedValue::Unnamed((0..32).map(DecodedValue::U128).collect());
+        let account = DecodedValue::Unnamed(vec![bytes]);
+        let expected: [u8; 32] = core::array::from_fn(|i| i as u8);
+        assert_eq!(account.as_account_id(), Some(AccountId32::from(expected)));
+        println!("✅ Account id decoded from newtype");
+    }
+
+    #[test]
+    fn test_one_byte_sequence() {
+        let byte = DecodedValue::Unnamed(vec![DecodedValue::U128(7)]);
+        assert_eq!(byte.as_bytes(), Some(vec![7]));
+        assert_eq!(byte.to_json(), serde_json::json!("0x07"));
+        // Wrapped in a newtype such as `BoundedVec`
+        assert_eq!(DecodedValue::Unnamed(vec![byte]).as_bytes(), Some(vec![7]));
+        println!("✅ One byte sequence test passed");
+    }
+
+    #[test]
+    fn test_account_ids_from_bounded_vec() {
+        let bounded = |accounts: Vec<DecodedValue>| DecodedValue::Unnamed(vec![DecodedValue::Unnamed(accounts)]);
+
+        assert_eq!(bounded(vec![]).as_account_ids(), vec![]);
+        assert_eq!(bounded(vec![account(1)]).as_account_ids(), vec![AccountId32::from([1u8; 32])]);
+        assert_eq!(
+            bounded(vec![account(1), account(2)]).as_account_ids(),
+            vec![AccountId32::from([1u8; 32]), AccountId32::from([2u8; 32])]
+        );
+        println!("✅ Account ids decoded from BoundedVec");
+    }
+
+    #[test]
+    fn test_value_from_json() {
+        use sp_core::crypto::Ss58Codec;
+
+        let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let value = value_from_json(&serde_json::json!([alice, "0x0102", 7, {"Staked": null}])).unwrap();
+
+        let expected = scale_value::Value::unnamed_composite([
+            scale_value::Value::from_bytes(AccountId32::from_ss58check(alice).unwrap()),
+            scale_value::Value::from_bytes([1u8, 2]),
+            scale_value::Value::u128(7),
+            scale_value::Value::unnamed_variant("Staked", []),
+        ]);
+        assert_eq!(value, expected);
+        assert!(value_from_json(&serde_json::json!(1.5)).is_err());
+        println!("✅ JSON to value conversion test passed");
+    }
+
+    #[test]
+    fn test_to_json() {
+        let value = DecodedValue::Named(vec![
+            ("who".to_string(), DecodedValue::Unnamed(vec![DecodedValue::U128(1), DecodedValue::U128(255)])),
+            ("amount".to_string(), DecodedValue::U128(u128::MAX)),
+            ("status".to_string(), DecodedValue::Variant("Free".to_string(), Box::default())),
+        ]);
+
+        assert_eq!(
+            value.to_json(),
+            serde_json::json!({
+                "who": "0x01ff",
+                "amount": u128::MAX.to_string(),
+                "status": "Free",
+            })
+        );
+        println!("✅ JSON rendering test passed");
+    }
+}
@@ -73,6 +73,13 @@ pub enum SDKError {
     Contract(String),
     /// Query errors
     Query(String),
+    /// More nomination targets than the runtime allows
+    TooManyNominations {
+        /// Number of targets submitted
+        requested: usize,
+        /// `Staking::MaxNominations` of the runtime
+        max: u32,
+    },
 }
 
 impl fmt::Display for SDKError {
@@ -108,6 +115,9 @@ impl fmt::Display for SDKError {
             SDKError::Transaction(msg) => write!(f, "Transaction error: {msg}"),
             SDKError::Contract(msg) => write!(f, "Contract error: {msg}"),
             SDKError::Query(msg) => write!(f, "Query error: {msg}"),
+            SDKError::TooManyNominations { requested, max } => {
+                write!(f, "Too many nominations: {requested} targets, at most {max} allowed")
+            }
         }
     }
 }
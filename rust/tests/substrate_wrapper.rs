@@ -15,10 +15,7 @@ const TEST_SEED: &str = "//Alice";
 #[ignore] // Run with: cargo test -- --ignored
 async fn test_connection_creation() -> Result<()> {
     // Test that we can create a connection
-    let connection = Connection::new(TEST_ENDPOINT).await?;
-    
-    // Connection creation should succeed
-    assert!(true, "Connection created successfully");
+    Connection::new(TEST_ENDPOINT).await?;
     
     Ok(())
 }
@@ -35,7 +32,7 @@ async fn test_keypair_creation() -> Result<()> {
     
     // Verify SS58 address generation
     let ss58 = keypair.to_ss58check();
-    assert!(ss58.len() > 0, "SS58 address should not be empty");
+    assert!(!ss58.is_empty(), "SS58 address should not be empty");
     
     println!("✅ Keypair created: {}", ss58);
     
@@ -74,9 +71,6 @@ async fn test_balance_query() -> Result<()> {
     // Query balance
     let balance = signed.get_balance().await?;
     
-    // Alice should have balance on testnet (or at least 0)
-    assert!(balance >= 0, "Balance should be non-negative");
-    
     println!("✅ Balance query successful: {} units", balance);
     
     Ok(())
@@ -166,8 +160,8 @@ async fn test_transfer_simulation() -> Result<()> {
     println!("   Note: Not actually executing transfer");
     
     // Actual transfer would be:
-    // let tx_hash = signed.transfer(recipient, 1_000_000_000_000).await?;
-    // println!("   Transaction: {:?}", tx_hash);
+    // let result = signed.transfer(recipient, 1_000_000_000_000).await?;
+    // println!("   Transaction: {:?}, success: {}", result.tx_hash, result.is_success());
     
     Ok(())
 }
@@ -195,3 +189,543 @@ async fn test_multiple_connections() -> Result<()> {
     
     Ok(())
 }
+
+#[tokio::test]
+#[ignore]
+async fn test_latest_block() -> Result<()> {
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+
+    let latest = connection.get_latest_block().await?;
+    let by_number = connection.get_block(latest.number).await?
+        .expect("Best block should be retrievable by number");
+
+    assert_eq!(latest.hash, by_number.hash);
+    println!("✅ Latest block: #{} ({:?})", latest.number, latest.hash);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_subscribe_finalized_blocks() -> Result<()> {
+    use futures::StreamExt;
+
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+    let mut blocks = connection.subscribe_finalized_blocks().await?;
+
+    let first = blocks.next().await.expect("Subscription ended")?;
+    let second = blocks.next().await.expect("Subscription ended")?;
+
+    assert_eq!(second.number, first.number + 1);
+    assert_eq!(second.parent_hash, first.hash);
+    println!("✅ Finalized blocks: #{} -> #{}", first.number, second.number);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_event_listener_attach() -> Result<()> {
+    use futures::StreamExt;
+    use selendra_sdk::substrate::{EventFilter, EventListener, EventPhase};
+
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+
+    // Every block starts with a Timestamp.set extrinsic, which emits System.ExtrinsicSuccess
+    let mut listener = EventListener::new();
+    listener.add_filter(
+        "extrinsic_success".to_string(),
+        EventFilter::new()
+            .pallet("System".to_string())
+            .event_name("ExtrinsicSuccess".to_string()),
+    );
+
+    let mut events = listener.attach(&connection).await?;
+    let event = events.next().await.expect("Event stream ended")?;
+
+    assert_eq!(event.pallet, "System");
+    assert_eq!(event.name, "ExtrinsicSuccess");
+    assert!(matches!(event.phase, EventPhase::ApplyExtrinsic(_)));
+    assert!(event.block_hash.is_some());
+    println!("✅ Event {}::{} in block {:?}", event.pallet, event.name, event.block_number);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_estimate_transfer_fee() -> Result<()> {
+    use selendra_sdk::substrate::Call;
+
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+    let keypair = keypair_from_string(TEST_SEED);
+    let signed = connection.sign(&keypair)?;
+
+    let call = Call::transfer_keep_alive(&signed.account_id(), 1_000_000_000_000);
+    let estimate = signed.estimate_fee(&call).await?;
+
+    assert!(estimate.partial_fee > 0);
+    assert_eq!(
+        estimate.partial_fee,
+        estimate.base_fee + estimate.len_fee + estimate.adjusted_weight_fee
+    );
+    println!("✅ Transfer fee: {} (weight {:?})", estimate.partial_fee, estimate.weight);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_batch_fee_estimate() -> Result<()> {
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+    let keypair = keypair_from_string(TEST_SEED);
+    let signed = connection.sign(&keypair)?;
+
+    let recipient = keypair_from_string("//Bob").account_id();
+    let batch = signed.batch_all()
+        .transfer(&recipient, 1_000_000_000_000)
+        .transfer(&recipient, 2_000_000_000_000);
+    assert_eq!(batch.len(), 2);
+
+    let estimate = batch.estimate_fee().await?;
+    assert!(estimate.partial_fee > 0);
+    println!("✅ Batch of {} transfers costs {}", batch.len(), estimate.partial_fee);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_multisig_pending_operations() -> Result<()> {
+    use selendra_sdk::substrate::MultisigAccount;
+
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+    let signatories = ["//Alice", "//Bob", "//Charlie"]
+        .iter()
+        .map(|seed| keypair_from_string(seed).account_id())
+        .collect();
+    let multisig = MultisigAccount::new(signatories, 2)?;
+
+    let pending = connection.get_multisigs(&multisig.account_id()).await?;
+    for operation in &pending {
+        assert!(operation.approvals.len() < multisig.threshold() as usize);
+    }
+    println!("✅ Multisig {} has {} pending operations", multisig.account_id(), pending.len());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_proxies_query() -> Result<()> {
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+    let account = keypair_from_string(TEST_SEED).account_id();
+
+    let info = connection.get_proxies(&account).await?;
+    if info.proxies.is_empty() {
+        assert_eq!(info.deposit, 0);
+    }
+    println!("✅ {} has {} proxies (deposit {})", account, info.proxies.len(), info.deposit);
+
+    Ok(())
+}
+
+#[test]
+fn test_offline_signing_roundtrip() -> Result<()> {
+    use selendra_sdk::substrate::{SignedPayload, SigningEra, SigningPayload};
+    use sp_core::H256;
+
+    // Online host: payload prepared with Connection::create_signing_payload
+    let keypair = keypair_from_string(TEST_SEED);
+    let payload = SigningPayload {
+        signer: keypair.account_id(),
+        call_data: vec![4, 0],
+        nonce: 0,
+        era: SigningEra::Immortal,
+        tip: 0,
+        spec_version: 1,
+        transaction_version: 1,
+        genesis_hash: H256::repeat_byte(1),
+        checkpoint_hash: H256::repeat_byte(1),
+        extensions: vec!["CheckSpecVersion".to_string(), "CheckGenesis".to_string(), "CheckNonce".to_string()],
+    };
+    let json = payload.to_json()?;
+
+    // Offline host: sign the transported payload
+    let signed = SigningPayload::from_json(&json)?.sign(&keypair)?;
+    let bytes = signed.to_bytes();
+
+    // Online host: assemble the extrinsic for Connection::submit_signed
+    let signed = SignedPayload::from_bytes(&bytes)?;
+    assert!(signed.verify());
+    assert_eq!(signed.payload, payload);
+    println!("✅ Offline signed extrinsic: {:?}", signed.tx_hash()?);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_storage_page_system_account() -> Result<()> {
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+
+    let page = connection.storage_page("System", "Account", vec![], None, 10).await?;
+    assert!(!page.entries.is_empty());
+
+    let entry = &page.entries[0];
+    let account = entry.keys[0].as_account_id().expect("System.Account is keyed by account id");
+    let fetched = connection
+        .storage_fetch_json("System", "Account", &[serde_json::json!(account.to_string())])
+        .await?
+        .expect("Listed account should exist");
+
+    assert_eq!(fetched, entry.value);
+    println!("✅ {} free balance: {:?}", account, fetched.field("data").and_then(|d| d.field("free")));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_queries_at_block() -> Result<()> {
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+    let finalized = connection.get_finalized_block().await?;
+    let past = finalized.number.saturating_sub(100);
+
+    let historical = connection.at(past).await?;
+    assert_eq!(historical.block(), connection.get_block_hash(past).await?);
+
+    let keypair = keypair_from_string(TEST_SEED);
+    let balance_then = historical.sign(&keypair)?.get_balance().await?;
+    let balance_now = connection.sign(&keypair)?.get_balance().await?;
+    println!("✅ Balance at #{}: {}, now: {}", past, balance_then, balance_now);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_chain_info() -> Result<()> {
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+
+    let version = connection.runtime_version().await?;
+    let info = connection.chain_info().await?;
+
+    assert_eq!(info.version, version.spec_version);
+    assert!(version.metadata_version >= 14);
+    println!(
+        "✅ {} spec {} ({:?}, {:?} decimals, ss58 {:?})",
+        info.chain_name,
+        version.spec_version,
+        info.token_symbol(),
+        info.token_decimals(),
+        info.ss58_prefix()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_unclaimed_eras() -> Result<()> {
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+    let keypair = keypair_from_string(TEST_SEED);
+    let account = connection.sign(&keypair)?.account_id();
+
+    let active_era = connection.get_active_era().await?;
+    let unclaimed = connection.get_unclaimed_eras(&account).await?;
+    let spans = connection.get_slashing_spans(&account).await?;
+
+    assert!(unclaimed.windows(2).all(|eras| eras[0] < eras[1]));
+    if let Some(active_era) = active_era {
+        assert!(unclaimed.iter().all(|era| *era < active_era));
+    }
+    println!(
+        "✅ Active era {:?}, {} unclaimed eras, {} slashing spans, payee {:?}",
+        active_era,
+        unclaimed.len(),
+        spans,
+        connection.get_payee(&account).await?
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_staking_analytics() -> Result<()> {
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+    let Some(active_era) = connection.get_active_era().await? else {
+        println!("⚠️ No active era yet");
+        return Ok(());
+    };
+    let era = active_era.saturating_sub(1);
+
+    let points = connection.get_era_reward_points(era).await?;
+    let returns = connection.get_validator_returns(era).await?;
+    assert!(returns.windows(2).all(|pair| pair[0].nominator_return >= pair[1].nominator_return));
+
+    if let Some(best) = returns.first() {
+        let exposure = connection.get_exposure(era, &best.validator).await?.expect("validator was elected");
+        assert_eq!(exposure.total, best.total_stake);
+
+        if let Some(nominator) = exposure.others.first() {
+            let estimate = connection.estimate_nominator_reward(&nominator.who, era).await?;
+            assert!(estimate.stake >= nominator.value);
+            println!("✅ Nominator {} earned ~{} in era {}", nominator.who, estimate.reward, era);
+        }
+        println!(
+            "✅ Best of {} validators in era {}: {} ({}% commission, {} points)",
+            returns.len(),
+            era,
+            best.validator,
+            best.prefs.commission_percent(),
+            best.points
+        );
+    }
+    assert!(returns.iter().all(|r| r.points == points.points_of(&r.validator)));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_nomination_pools_query() -> Result<()> {
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+    let keypair = keypair_from_string(TEST_SEED);
+    let account = connection.sign(&keypair)?.account_id();
+
+    let pools = connection.get_pools().await?;
+    assert!(pools.windows(2).all(|pair| pair[0].id < pair[1].id));
+
+    if let Some(pool) = pools.first() {
+        assert_eq!(connection.get_pool(pool.id).await?.as_ref(), Some(pool));
+        assert!(connection.get_reward_pool(pool.id).await?.is_some());
+        println!(
+            "✅ Pool {} \"{}\": {:?}, {} members, stash {}",
+            pool.id,
+            connection.get_pool_metadata(pool.id).await?,
+            pool.state,
+            pool.member_counter,
+            connection.pool_bonded_account(pool.id)
+        );
+    }
+
+    match connection.get_pool_member(&account).await? {
+        Some(member) => println!(
+            "✅ Member of pool {} with {} points, {} pending rewards",
+            member.pool_id,
+            member.points,
+            connection.get_pending_rewards(&account).await?
+        ),
+        None => println!("✅ {} pools, test account is not a member", pools.len()),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_aleph_committee_and_bans() -> Result<()> {
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+    let session = connection.get_current_session().await?;
+    let version = connection.get_finality_version().await?;
+    let committee = connection.get_session_committee(session).await?;
+    let (current, _next) = connection.get_session_validators().await?;
+
+    assert!(!committee.finality_committee.is_empty());
+    for validator in &committee.block_producers {
+        assert!(current.contains(validator));
+    }
+
+    if let Some(validator) = committee.block_producers.first() {
+        let risk = connection.get_ban_risk(validator).await?;
+        println!(
+            "✅ {} is {} sessions from a ban (banned: {:?})",
+            validator,
+            risk.sessions_until_ban(),
+            risk.banned
+        );
+    }
+    println!(
+        "✅ Session {}: {} finalizers, {} producers, finality version {}",
+        session,
+        committee.finality_committee.len(),
+        committee.block_producers.len(),
+        version.current
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_wait_for_finalized() -> Result<()> {
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+    let best = connection.get_latest_block().await?;
+
+    let finalized = tokio::time::timeout(
+        std::time::Duration::from_secs(60),
+        connection.wait_for_finalized(best.hash),
+    )
+    .await
+    .expect("block finalized within a minute")?;
+
+    assert_eq!(finalized.block.number, best.number);
+    assert!(connection.get_finalized_block().await?.number >= best.number);
+    println!(
+        "✅ Block #{} finalized (justification: {})",
+        best.number,
+        finalized.justification.is_some()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_treasury_overview() -> Result<()> {
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+
+    let info = connection.get_treasury_info().await?;
+    let proposals = connection.get_treasury_proposals().await?;
+
+    assert!(info.spend_period == 0 || info.next_spend_period % info.spend_period == 0);
+    assert!(proposals.len() as u32 <= info.proposal_count);
+    for proposal in &proposals {
+        assert_eq!(proposal.approved, info.approvals.contains(&proposal.id));
+    }
+    println!(
+        "✅ Treasury {} holds {} (burn {} at block {}), {} open proposals",
+        info.account,
+        info.pot,
+        info.projected_burn(),
+        info.next_spend_period,
+        proposals.len()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_democracy_query() -> Result<()> {
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+    let keypair = keypair_from_string(TEST_SEED);
+    let account = connection.sign(&keypair)?.account_id();
+
+    let proposals = connection.get_public_proposals().await?;
+    let referenda = connection.get_ongoing_referenda().await?;
+    assert!(referenda.iter().all(|referendum| referendum.is_ongoing()));
+
+    if let Some(referendum) = referenda.first() {
+        assert_eq!(connection.get_referendum(referendum.index).await?.as_ref(), Some(referendum));
+    }
+
+    let voting = connection.get_voting_state(&account).await?;
+    println!(
+        "✅ {} public proposals, {} ongoing referenda, {} votes cast, {} locked",
+        proposals.len(),
+        referenda.len(),
+        voting.votes.len(),
+        voting.locked
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_resolve_display_names() -> Result<()> {
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+    let validators = connection.get_current_era_validators().await?;
+
+    let names = connection.resolve_display_names(&validators).await?;
+    assert!(names.keys().all(|account| validators.contains(account)));
+
+    for validator in validators.iter().take(5) {
+        let identity = connection.identity_of(validator).await?;
+        let parent = connection.super_of(validator).await?;
+        if let Some(identity) = &identity {
+            assert_eq!(names.get(validator), identity.info.display.as_ref());
+        }
+        println!(
+            "✅ {}: {:?} (verified: {}, sub of: {:?})",
+            validator,
+            names.get(validator),
+            identity.is_some_and(|identity| identity.is_verified()),
+            parent.map(|parent| parent.parent)
+        );
+    }
+    println!("✅ Resolved {} of {} validator names", names.len(), validators.len());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_vesting_balance() -> Result<()> {
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+    let keypair = keypair_from_string(TEST_SEED);
+    let signed = connection.sign(&keypair)?;
+    let account = signed.account_id();
+
+    let schedules = connection.vesting_schedules(&account).await?;
+    let balance = connection.get_vesting_balance(&account, None).await?;
+    assert_eq!(balance.total, schedules.iter().map(|schedule| schedule.locked).sum::<u128>());
+    assert_eq!(balance.vested + balance.locked, balance.total);
+
+    let info = signed.get_account_info().await?;
+    for (reason, amount) in info.lock_reasons() {
+        println!("✅ Locked by {}: {}", reason, amount);
+    }
+    println!(
+        "✅ {} vesting schedules, {} vested, {} locked, {} claimable at block {}",
+        schedules.len(),
+        balance.vested,
+        balance.locked,
+        balance.claimable,
+        balance.block
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_account_balances() -> Result<()> {
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+    let keypair = keypair_from_string(TEST_SEED);
+    let signed = connection.sign(&keypair)?;
+    let account = signed.account_id();
+
+    let info = connection.get_account_info(&account).await?;
+    assert_eq!(info.data.free, signed.get_account_info().await?.data.free);
+    assert_eq!(info.data.free, connection.get_free_balance(&account).await?);
+
+    let existential_deposit = connection.get_existential_deposit().await?;
+    let transferable = connection.get_transferable_balance(&account).await?;
+    assert!(transferable <= info.data.free.saturating_sub(existential_deposit));
+    assert!(transferable <= info.transferable(existential_deposit, false));
+    println!(
+        "✅ Free {}, reserved {}, frozen {}, transferable {} (existential deposit {})",
+        info.data.free, info.data.reserved, info.data.frozen, transferable, existential_deposit
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_next_nonce() -> Result<()> {
+    let connection = Connection::new(TEST_ENDPOINT).await?;
+    let keypair = keypair_from_string(TEST_SEED);
+    let signed = connection.sign(&keypair)?;
+
+    // The pool may hold pending transactions beyond the last included nonce
+    let next = connection.get_next_nonce(&signed.account_id()).await?;
+    assert!(next >= signed.get_account_info().await?.nonce);
+    println!("✅ Next nonce of {} is {}", signed.account_id(), next);
+
+    Ok(())
+}